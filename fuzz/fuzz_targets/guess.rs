@@ -0,0 +1,18 @@
+//! Feeds arbitrary UTF-8 input line by line as guesses into a game
+#![no_main]
+
+use hangman_rs_web::game::{base_game::Game, guess::Guess, MAX_LIVES};
+use libfuzzer_sys::fuzz_target;
+use uuid::Uuid;
+
+fuzz_target!(|input: &str| {
+    let mut game = Game::with_word(Uuid::new_v4(), Uuid::new_v4(), "FLUSS-UFER", MAX_LIVES);
+    for line in input.lines() {
+        if let Ok(guess) = line.parse::<Guess>() {
+            let result = game.guess(guess);
+            assert!((1..=6).contains(&result));
+            assert!((0..=MAX_LIVES).contains(&game.lives()));
+            assert_eq!(game.game_string().split(' ').count(), 10);
+        }
+    }
+});
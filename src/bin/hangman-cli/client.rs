@@ -0,0 +1,178 @@
+use std::{fmt::Display, fs, io, path::PathBuf};
+
+use serde::Deserialize;
+use ureq::Agent;
+
+/// Errors that can occur when communicating with the server
+#[derive(Debug)]
+pub enum ClientError {
+    /// The server could not be reached or responded with an error status
+    Http(Box<ureq::Error>),
+    /// The response of the server could not be read
+    Io(io::Error),
+    /// The server did not return a token when registering
+    MissingToken,
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::Http(err) => write!(f, "Request failed: {}", err),
+            ClientError::Io(err) => write!(f, "Unable to read response: {}", err),
+            ClientError::MissingToken => write!(f, "Server did not return a token"),
+        }
+    }
+}
+
+impl From<ureq::Error> for ClientError {
+    fn from(err: ureq::Error) -> Self {
+        ClientError::Http(Box::new(err))
+    }
+}
+
+impl From<io::Error> for ClientError {
+    fn from(err: io::Error) -> Self {
+        ClientError::Io(err)
+    }
+}
+
+/// State of the game the player is registered to, see `/api/registered`
+#[derive(Debug, PartialEq, Eq)]
+pub enum Registration {
+    /// The stored token is not assigned to a game
+    None,
+    /// The game is running
+    Playing,
+    /// The game has ended and was won
+    Won,
+    /// The game has ended and was lost
+    Lost,
+}
+
+/// Response of the server to a guess
+pub enum GuessResponse {
+    /// The result code of [Game::guess](../../hangman_rs_web/game/base_game/struct.Game.html#method.guess)
+    Result(i32),
+    /// The server rejected the guess without costing a life, contains the reason
+    Rejected(String),
+}
+
+/// Snapshot of the game that is shown to the player
+pub struct GameView {
+    pub game_string: String,
+    pub guessed_letters: String,
+    pub lives: i32,
+    pub max_lives: i32,
+}
+
+/// Response of `/api/register`
+#[derive(Deserialize)]
+struct RegistrationData {
+    token: String,
+}
+
+/// Talks to the REST api of the server.
+///
+/// The token that authenticates the player is stored in the session file so that a game can be resumed later.
+pub struct Client {
+    agent: Agent,
+    server: String,
+    session_file: PathBuf,
+    token: Option<String>,
+}
+
+impl Client {
+    /// Creates a new client for the server and loads the session from the session file if it belongs to this server.
+    ///
+    /// The session file contains the server address in the first line and the token in the second line.
+    pub fn new(server: &str, session_file: PathBuf) -> Self {
+        let server = server.trim_end_matches('/').to_string();
+        let token = fs::read_to_string(&session_file).ok().and_then(|content| {
+            let mut lines = content.lines();
+            match (lines.next(), lines.next()) {
+                (Some(stored_server), Some(token)) if stored_server == server => Some(token.to_string()),
+                _ => None,
+            }
+        });
+        Self {
+            agent: Agent::new(),
+            server,
+            session_file,
+            token,
+        }
+    }
+
+    /// Registers a new game and stores the new token in the session file
+    pub fn register(&mut self) -> Result<(), ClientError> {
+        let registration: RegistrationData = self.agent.post(&self.url("/api/register")).call()?.into_json()?;
+        if registration.token.is_empty() {
+            return Err(ClientError::MissingToken);
+        }
+        fs::write(&self.session_file, format!("{}\n{}\n", self.server, registration.token))?;
+        self.token = Some(registration.token);
+        Ok(())
+    }
+
+    /// Checks if the stored token is assigned to a game
+    pub fn registered(&self) -> Result<Registration, ClientError> {
+        if self.token.is_none() {
+            return Ok(Registration::None);
+        }
+        Ok(match self.get("/api/registered")?.as_str() {
+            "playing" => Registration::Playing,
+            "won" => Registration::Won,
+            "lost" => Registration::Lost,
+            _ => Registration::None,
+        })
+    }
+
+    /// Submits a letter or word, returns the result code of the server or the reason why the guess was rejected
+    pub fn guess(&self, guess: &str) -> Result<GuessResponse, ClientError> {
+        match self.with_token(self.agent.post(&self.url("/api/guess"))).send_json(guess) {
+            Ok(response) => Ok(GuessResponse::Result(response.into_string()?.trim().parse().unwrap_or(0))),
+            Err(ureq::Error::Status(400, response)) => Ok(GuessResponse::Rejected(response.into_json()?)),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Retrieves everything that is needed to show the game
+    pub fn view(&self) -> Result<GameView, ClientError> {
+        Ok(GameView {
+            game_string: self.get("/api/game_string")?,
+            guessed_letters: self.get("/api/guessed_letters")?,
+            lives: self.get("/api/lives")?.parse().unwrap_or(0),
+            max_lives: self.get("/api/max_lives")?.parse().unwrap_or(0),
+        })
+    }
+
+    /// The word of the game, only available when the game has ended
+    pub fn word(&self) -> Result<String, ClientError> {
+        self.get("/api/word")
+    }
+
+    /// Deletes the current game and the stored session
+    pub fn delete_game(&mut self) -> Result<(), ClientError> {
+        self.get("/api/delete_game")?;
+        self.token = None;
+        let _ = fs::remove_file(&self.session_file);
+        Ok(())
+    }
+
+    /// Sends a get request to the path and returns the response as text
+    fn get(&self, path: &str) -> Result<String, ClientError> {
+        let response = self.with_token(self.agent.get(&self.url(path))).call()?;
+        Ok(response.into_string()?)
+    }
+
+    /// Adds the `Authorization` header to the request if a token is set
+    fn with_token(&self, request: ureq::Request) -> ureq::Request {
+        match &self.token {
+            Some(token) => request.set("Authorization", &format!("Bearer {}", token)),
+            None => request,
+        }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.server, path)
+    }
+}
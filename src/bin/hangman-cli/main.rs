@@ -0,0 +1,122 @@
+//! Terminal client for the hangman server.
+//!
+//! Usage: `hangman-cli [--server <url>] [--session <file>] [--new]`
+//!
+//! The client talks to the REST api of the server, the game can be resumed by starting the client again.
+use std::{env, io::{self, BufRead, Write}, path::PathBuf, process};
+
+use client::{Client, ClientError, GuessResponse, Registration};
+use hangman_rs_web::gallows;
+
+/// Communication with the REST api
+mod client;
+
+/// Server that is used when `--server` is not set
+const DEFAULT_SERVER: &str = "http://127.0.0.1:11511";
+/// Name of the session file in the home directory when `--session` is not set
+const DEFAULT_SESSION_FILE: &str = ".hangman-cli-session";
+
+/// Options that can be set on the command line
+struct Options {
+    server: String,
+    session_file: PathBuf,
+    /// Start a new game even if a running game could be resumed
+    new_game: bool,
+}
+
+fn main() {
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(msg) => {
+            eprintln!("{}", msg);
+            eprintln!("Usage: hangman-cli [--server <url>] [--session <file>] [--new]");
+            process::exit(2);
+        }
+    };
+    let mut client = Client::new(&options.server, options.session_file);
+    if let Err(err) = run(&mut client, options.new_game) {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
+
+/// Parses the command line arguments
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let home = env::var("HOME").map(PathBuf::from).unwrap_or_default();
+    let mut options = Options {
+        server: String::from(DEFAULT_SERVER),
+        session_file: home.join(DEFAULT_SESSION_FILE),
+        new_game: false,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--server" => options.server = args.next().ok_or("Missing value for --server")?,
+            "--session" => options.session_file = PathBuf::from(args.next().ok_or("Missing value for --session")?),
+            "--new" => options.new_game = true,
+            _ => return Err(format!("Unknown argument: {}", arg)),
+        }
+    }
+    Ok(options)
+}
+
+/// Resumes or registers a game and plays until the player quits
+fn run(client: &mut Client, new_game: bool) -> Result<(), ClientError> {
+    let mut registration = client.registered()?;
+    if new_game && registration != Registration::None {
+        client.delete_game()?;
+        registration = Registration::None;
+    }
+    match registration {
+        Registration::None => client.register()?,
+        Registration::Playing => println!("Resuming your game."),
+        Registration::Won | Registration::Lost => {},
+    }
+    println!("Type a letter or a word to guess, :new to start a new game and :quit to leave (the game can be resumed).");
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        show(client)?;
+        match client.registered()? {
+            Registration::Won => println!("You won! Type :new to play again."),
+            Registration::Lost => println!("You lost! The word was {}. Type :new to play again.", client.word()?),
+            Registration::None => {
+                println!("Your game does not exist anymore, registering a new game.");
+                client.register()?;
+                continue;
+            },
+            Registration::Playing => {},
+        }
+        print!("> ");
+        io::stdout().flush()?;
+        let Some(line) = lines.next() else {
+            return Ok(());
+        };
+        let input = line?.trim().to_string();
+        match input.as_str() {
+            "" => continue,
+            ":quit" | ":q" => return Ok(()),
+            ":new" => {
+                client.delete_game()?;
+                client.register()?;
+            },
+            guess => match client.guess(guess)? {
+                GuessResponse::Result(5) => println!("This letter was already guessed."),
+                GuessResponse::Result(3 | 4) => println!("Wrong!"),
+                GuessResponse::Result(_) => {},
+                GuessResponse::Rejected(reason) => println!("{}", reason),
+            },
+        }
+    }
+}
+
+/// Prints the gallows, the game string and the guessed letters
+fn show(client: &Client) -> Result<(), ClientError> {
+    let view = client.view()?;
+    println!();
+    println!("{}", gallows::render(view.lives, view.max_lives));
+    println!();
+    println!("Word:    {}", view.game_string);
+    println!("Guessed: {}", view.guessed_letters.split_whitespace().collect::<Vec<&str>>().join(" "));
+    println!("Lives:   {}/{}", view.lives, view.max_lives);
+    Ok(())
+}
@@ -0,0 +1,282 @@
+//! Local hangman game that runs in the terminal without the server.
+//!
+//! Usage: `hangman-tui [--words <file>]... [--difficulty <easy|medium|hard>] [--category <name>] [--stats <file>]`
+//!
+//! Each words file is a category, the name of the category is the file name without extension.
+use std::{env, fmt::Display, io::{self, BufRead, Lines, StdinLock, Write}, path::{Path, PathBuf}, process};
+
+use hangman_rs_web::{gallows, game::{base_game::Game, guess::Guess, word_list::WordList}};
+use rand::seq::SliceRandom;
+use stats::Stats;
+use uuid::Uuid;
+
+/// Statistics that are kept between games
+mod stats;
+
+/// Words file that is used when no `--words` is set
+const DEFAULT_WORDS_FILE: &str = "words.txt";
+/// Name of the stats file in the home directory when `--stats` is not set
+const DEFAULT_STATS_FILE: &str = ".hangman-tui-stats";
+
+/// The difficulty determines how long the words are and how many lives the player has
+#[derive(Clone, Copy)]
+enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard];
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "easy" => Some(Difficulty::Easy),
+            "medium" => Some(Difficulty::Medium),
+            "hard" => Some(Difficulty::Hard),
+            _ => None,
+        }
+    }
+
+    /// The amount of lives the player starts with
+    fn lives(&self) -> i32 {
+        match self {
+            Difficulty::Easy => 10,
+            Difficulty::Medium => 7,
+            Difficulty::Hard => 5,
+        }
+    }
+
+    /// Checks if the length of the word fits the difficulty
+    fn accepts(&self, word: &str) -> bool {
+        let length = word.chars().count();
+        match self {
+            Difficulty::Easy => length <= 8,
+            Difficulty::Medium => (6..=12).contains(&length),
+            Difficulty::Hard => length >= 10,
+        }
+    }
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Difficulty::Easy => write!(f, "easy"),
+            Difficulty::Medium => write!(f, "medium"),
+            Difficulty::Hard => write!(f, "hard"),
+        }
+    }
+}
+
+/// Words of a single words file
+struct Category {
+    name: String,
+    words: Vec<String>,
+}
+
+/// Options that can be set on the command line
+struct Options {
+    words_files: Vec<PathBuf>,
+    difficulty: Option<Difficulty>,
+    category: Option<String>,
+    stats_file: PathBuf,
+}
+
+fn main() {
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(msg) => {
+            eprintln!("{}", msg);
+            eprintln!("Usage: hangman-tui [--words <file>]... [--difficulty <easy|medium|hard>] [--category <name>] [--stats <file>]");
+            process::exit(2);
+        }
+    };
+    let mut categories = Vec::new();
+    for path in &options.words_files {
+        match WordList::load(path) {
+            Ok((words, errors)) => {
+                for err in errors {
+                    eprintln!("{}: {}", path.display(), err);
+                }
+                categories.push(Category {
+                    name: category_name(path),
+                    words: words.words().to_vec(),
+                });
+            },
+            Err(err) => {
+                eprintln!("{}: {}", path.display(), err);
+                process::exit(1);
+            }
+        }
+    }
+    if let Err(err) = run(&options, &categories) {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
+
+/// Parses the command line arguments
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let home = env::var("HOME").map(PathBuf::from).unwrap_or_default();
+    let mut options = Options {
+        words_files: Vec::new(),
+        difficulty: None,
+        category: None,
+        stats_file: home.join(DEFAULT_STATS_FILE),
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--words" => options.words_files.push(PathBuf::from(args.next().ok_or("Missing value for --words")?)),
+            "--difficulty" => {
+                let name = args.next().ok_or("Missing value for --difficulty")?;
+                options.difficulty = Some(Difficulty::from_name(&name).ok_or(format!("Unknown difficulty: {}", name))?);
+            },
+            "--category" => options.category = Some(args.next().ok_or("Missing value for --category")?),
+            "--stats" => options.stats_file = PathBuf::from(args.next().ok_or("Missing value for --stats")?),
+            _ => return Err(format!("Unknown argument: {}", arg)),
+        }
+    }
+    if options.words_files.is_empty() {
+        options.words_files.push(PathBuf::from(DEFAULT_WORDS_FILE));
+    }
+    Ok(options)
+}
+
+/// The name of the category that is stored in the file
+fn category_name(path: &Path) -> String {
+    path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_else(|| path.display().to_string())
+}
+
+/// Plays games until the player does not want to play again
+fn run(options: &Options, categories: &[Category]) -> io::Result<()> {
+    let mut stats = Stats::load(&options.stats_file);
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        let category = match &options.category {
+            Some(name) => match categories.iter().find(|category| &category.name == name) {
+                Some(category) => category,
+                None => return Err(io::Error::new(io::ErrorKind::NotFound, format!("Unknown category: {}", name))),
+            },
+            None if categories.len() == 1 => &categories[0],
+            None => {
+                let names: Vec<&str> = categories.iter().map(|category| category.name.as_str()).collect();
+                match choose(&mut lines, "Category", &names)? {
+                    Some(index) => &categories[index],
+                    None => return Ok(()),
+                }
+            },
+        };
+        let difficulty = match options.difficulty {
+            Some(difficulty) => difficulty,
+            None => {
+                let names: Vec<String> = Difficulty::ALL.iter().map(|difficulty| difficulty.to_string()).collect();
+                let names: Vec<&str> = names.iter().map(String::as_str).collect();
+                match choose(&mut lines, "Difficulty", &names)? {
+                    Some(index) => Difficulty::ALL[index],
+                    None => return Ok(()),
+                }
+            },
+        };
+        let mut candidates: Vec<&String> = category.words.iter().filter(|word| difficulty.accepts(word)).collect();
+        if candidates.is_empty() {
+            // Use the whole category instead of refusing to play
+            candidates = category.words.iter().collect();
+        }
+        let Some(word) = candidates.choose(&mut rand::thread_rng()) else {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Category {} does not contain any words", category.name)));
+        };
+        let mut game = Game::with_word(Uuid::new_v4(), Uuid::new_v4(), word, difficulty.lives());
+        let Some(won) = play(&mut game, &mut lines, &format!("Category: {} | Difficulty: {}", category.name, difficulty), difficulty.lives())? else {
+            return Ok(());
+        };
+        stats.record(won)?;
+        println!("{}", stats);
+        print!("Play again? [Y/n] ");
+        io::stdout().flush()?;
+        match lines.next().transpose()? {
+            Some(answer) if !answer.trim().to_lowercase().starts_with('n') => (),
+            _ => return Ok(()),
+        }
+    }
+}
+
+/// Plays the game until it is completed.
+///
+/// # Returns
+/// `Some(bool)` when the game has been completed, the boolean indicates if the game was won
+///
+/// `None` when the player quit the game
+fn play(game: &mut Game, lines: &mut Lines<StdinLock>, header: &str, max_lives: i32) -> io::Result<Option<bool>> {
+    let mut message = String::from("Type a letter or a word to guess, :quit to leave.");
+    loop {
+        // Clear the screen and move the cursor to the top left corner
+        print!("\x1b[2J\x1b[H");
+        println!("{}", header);
+        println!();
+        println!("{}", gallows::render(game.lives(), max_lives));
+        println!();
+        println!("Word:    {}", game.game_string());
+        println!("Guessed: {}", game.guessed_letters().split_whitespace().collect::<Vec<&str>>().join(" "));
+        println!("Lives:   {}/{}", game.lives(), max_lives);
+        println!();
+        if let Some(won) = game.completed() {
+            match won {
+                true => println!("You won!"),
+                false => println!("You lost! The word was {}", game.word().unwrap_or_default()),
+            }
+            return Ok(Some(won));
+        }
+        println!("{}", message);
+        print!("> ");
+        io::stdout().flush()?;
+        let Some(line) = lines.next() else {
+            return Ok(None);
+        };
+        let input = line?.trim().to_string();
+        message = match input.as_str() {
+            "" => String::new(),
+            ":quit" | ":q" => return Ok(None),
+            input => match input.parse::<Guess>() {
+                Ok(guess) => match game.guess(guess) {
+                    2 => String::from("Correct!"),
+                    3 | 4 => String::from("Wrong!"),
+                    5 => String::from("This letter was already guessed."),
+                    _ => String::new(),
+                },
+                Err(err) => err.to_string(),
+            },
+        };
+    }
+}
+
+/// Asks the player to choose one of the options.
+///
+/// # Returns
+/// `Some(usize)` the index of the chosen option
+///
+/// `None` when the input has ended
+fn choose(lines: &mut Lines<StdinLock>, title: &str, options: &[&str]) -> io::Result<Option<usize>> {
+    loop {
+        println!("{}:", title);
+        for (index, option) in options.iter().enumerate() {
+            println!("  {}) {}", index + 1, option);
+        }
+        print!("> ");
+        io::stdout().flush()?;
+        let Some(line) = lines.next() else {
+            return Ok(None);
+        };
+        let line = line?;
+        let input = line.trim();
+        if let Ok(number) = input.parse::<usize>() {
+            if (1..=options.len()).contains(&number) {
+                return Ok(Some(number - 1));
+            }
+        }
+        if let Some(index) = options.iter().position(|option| option.eq_ignore_ascii_case(input)) {
+            return Ok(Some(index));
+        }
+        println!("Invalid choice: {}", input);
+    }
+}
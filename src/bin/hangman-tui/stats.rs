@@ -0,0 +1,66 @@
+use std::{fmt::Display, fs, io, path::{Path, PathBuf}};
+
+/// Statistics of all games that have been played locally.
+///
+/// The statistics are stored as `key=value` lines in the stats file.
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub played: u32,
+    pub won: u32,
+    /// Number of games that have been won in a row
+    pub streak: u32,
+    /// The longest streak that was ever reached
+    pub best_streak: u32,
+    /// File from which the statistics where loaded
+    path: PathBuf,
+}
+
+impl Stats {
+    /// Loads the statistics from the file, missing files or unknown keys are ignored.
+    pub fn load(path: &Path) -> Self {
+        let mut stats = Stats {
+            path: path.to_path_buf(),
+            ..Default::default()
+        };
+        let content = fs::read_to_string(path).unwrap_or_default();
+        for line in content.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let Ok(value) = value.trim().parse::<u32>() else {
+                continue;
+            };
+            match key.trim() {
+                "played" => stats.played = value,
+                "won" => stats.won = value,
+                "streak" => stats.streak = value,
+                "best_streak" => stats.best_streak = value,
+                _ => (),
+            }
+        }
+        stats
+    }
+
+    /// Records the result of a game and writes the statistics to the stats file
+    pub fn record(&mut self, won: bool) -> io::Result<()> {
+        self.played += 1;
+        if won {
+            self.won += 1;
+            self.streak += 1;
+            self.best_streak = self.best_streak.max(self.streak);
+        } else {
+            self.streak = 0;
+        }
+        fs::write(&self.path, format!("played={}\nwon={}\nstreak={}\nbest_streak={}\n", self.played, self.won, self.streak, self.best_streak))
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rate = match self.played {
+            0 => 0,
+            played => self.won * 100 / played,
+        };
+        write!(f, "Played: {} | Won: {} ({}%) | Streak: {} | Best streak: {}", self.played, self.won, rate, self.streak, self.best_streak)
+    }
+}
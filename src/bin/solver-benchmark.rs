@@ -0,0 +1,30 @@
+//! Reports how hard each word of a words file is for a player that knows all words and always guesses the best letter.
+//!
+//! Usage: `solver-benchmark [file]`, `words.txt` is used when no file is given.
+//!
+//! The words are printed hardest first with the number of wrong guesses and guesses the
+//! [Solver](../hangman_rs_web/game/solver/struct.Solver.html) needed, words that it could not solve are marked.
+use std::{env, process};
+
+use hangman_rs_web::game::{solver::solve, word_list::WordList, MAX_LIVES};
+
+fn main() {
+    let file = env::args().nth(1).unwrap_or_else(|| String::from("words.txt"));
+    let list = match WordList::load(&file) {
+        Ok((list, _)) => list,
+        Err(err) => {
+            println!("{}: {}", file, err);
+            process::exit(1);
+        },
+    };
+    let mut results: Vec<_> = list.words().iter().map(|word| (word, solve(list.words(), word))).collect();
+    results.sort_by(|(a_word, a), (b_word, b)| b.wrong.cmp(&a.wrong).then(b.guesses.cmp(&a.guesses)).then(a_word.cmp(b_word)));
+    println!("{:<30} {:>6} {:>8}", "word", "wrong", "guesses");
+    for (word, result) in &results {
+        println!("{:<30} {:>6} {:>8}{}", word, result.wrong, result.guesses, if result.won { "" } else { "  not solved" });
+    }
+    let solved = results.iter().filter(|(_, result)| result.won).count();
+    let wrong = results.iter().map(|(_, result)| result.wrong).sum::<u32>();
+    println!();
+    println!("{} of {} words solved with {} lives, {:.2} wrong guesses on average", solved, results.len(), MAX_LIVES, wrong as f64 / results.len().max(1) as f64);
+}
@@ -0,0 +1,34 @@
+//! Checks words files for lines that would be skipped when the file is loaded by the server.
+//!
+//! Usage: `validate-words [file]...`, `words.txt` is checked when no file is given.
+//!
+//! Exits with status `1` when a file could not be loaded or contains invalid lines, so that it can be used in CI.
+use std::{env, process};
+
+use hangman_rs_web::game::word_list::WordList;
+
+fn main() {
+    let mut files: Vec<String> = env::args().skip(1).collect();
+    if files.is_empty() {
+        files.push(String::from("words.txt"));
+    }
+    let mut failed = false;
+    for file in &files {
+        match WordList::load(file) {
+            Ok((list, errors)) => {
+                for err in &errors {
+                    println!("{}: {}", file, err);
+                }
+                println!("{}: {} valid words, {} invalid lines", file, list.len(), errors.len());
+                failed |= !errors.is_empty();
+            },
+            Err(err) => {
+                println!("{}: {}", file, err);
+                failed = true;
+            },
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
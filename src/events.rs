@@ -0,0 +1,71 @@
+use std::{collections::HashMap, pin::Pin, sync::Mutex};
+
+use rocket::{futures::{stream, Stream}, response::stream::{Event, EventStream}, tokio::{select, sync::broadcast::{self, error::RecvError, Receiver, Sender}}, Shutdown};
+use rocket_okapi::JsonSchema;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::request_data::ChatMessageView;
+
+/// Number of events that are buffered per game for subscribers that are slow to receive them
+const EVENT_BUFFER: usize = 32;
+
+/// Server-sent event stream of a game, the stream ends when the server shuts down
+pub type GameEventStream = EventStream<Pin<Box<dyn Stream<Item = Event> + Send>>>;
+
+/// Event of a game that is delivered to the players over the event stream
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GameEvent {
+    /// A player has sent a chat message
+    Chat(ChatMessageView),
+}
+
+/// Delivers the events of the games to the subscribed event streams.
+///
+/// One `Events` instance is managed by rocket, a channel is only kept while a game has subscribers.
+#[derive(Default)]
+pub struct Events {
+    channels: Mutex<HashMap<Uuid, Sender<GameEvent>>>,
+}
+
+impl Events {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Subscribes to the events of the game
+    pub fn subscribe(&self, game_id: Uuid) -> Receiver<GameEvent> {
+        let mut channels = self.channels.lock().unwrap();
+        channels.retain(|_, sender| sender.receiver_count() > 0);
+        channels.entry(game_id).or_insert_with(|| broadcast::channel(EVENT_BUFFER).0).subscribe()
+    }
+
+    /// Sends the event to all subscribers of the game, the event is dropped when nobody is subscribed
+    pub fn publish(&self, game_id: Uuid, event: GameEvent) {
+        if let Some(sender) = self.channels.lock().unwrap().get(&game_id) {
+            // Fails only when all subscribers have disconnected in the meantime
+            let _ = sender.send(event);
+        }
+    }
+
+    /// Subscribes to the events of the game and delivers them as server-sent events.
+    ///
+    /// Events that a slow client has missed because the buffer was full are skipped.
+    pub fn stream(&self, game_id: Uuid, shutdown: Shutdown) -> GameEventStream {
+        let events = stream::unfold((self.subscribe(game_id), shutdown), |(mut receiver, mut shutdown)| async move {
+            loop {
+                let event = select! {
+                    event = receiver.recv() => match event {
+                        Ok(event) => event,
+                        Err(RecvError::Closed) => return None,
+                        Err(RecvError::Lagged(_)) => continue,
+                    },
+                    _ = &mut shutdown => return None,
+                };
+                return Some((Event::json(&event), (receiver, shutdown)));
+            }
+        });
+        EventStream::from(Box::pin(events) as Pin<Box<dyn Stream<Item = Event> + Send>>)
+    }
+}
@@ -0,0 +1,73 @@
+/// Height of the drawing in lines
+const HEIGHT: usize = 7;
+/// Width of the drawing in characters
+const WIDTH: usize = 10;
+
+/// All parts of the gallows in the order in which they are drawn.
+///
+/// Each part consists of characters that are placed at `(line, column)`.
+const PARTS: [&[(usize, usize, char)]; 11] = [
+    // Base
+    &[(6, 0, '='), (6, 1, '='), (6, 2, '='), (6, 3, '='), (6, 4, '='), (6, 5, '='), (6, 6, '='), (6, 7, '='), (6, 8, '='), (6, 9, '=')],
+    // Pole
+    &[(0, 2, '+'), (1, 2, '|'), (2, 2, '|'), (3, 2, '|'), (4, 2, '|'), (5, 2, '|')],
+    // Beam
+    &[(0, 3, '-'), (0, 4, '-'), (0, 5, '-'), (0, 6, '-'), (0, 7, '-'), (0, 8, '+')],
+    // Brace
+    &[(1, 3, '/')],
+    // Rope
+    &[(1, 8, '|')],
+    // Head
+    &[(2, 8, 'O')],
+    // Body
+    &[(3, 8, '|'), (4, 8, '|')],
+    // Arms
+    &[(3, 7, '/')],
+    &[(3, 9, '\\')],
+    // Legs
+    &[(5, 7, '/')],
+    &[(5, 9, '\\')],
+];
+
+/// Renders the gallows for the amount of lives left.
+///
+/// The parts are spread over all lives so that the drawing is complete when no lives are left.
+pub fn render(lives_left: i32, max_lives: i32) -> String {
+    let visible = match max_lives > 0 {
+        true => {
+            let mistakes = (max_lives - lives_left.clamp(0, max_lives)) as usize;
+            (mistakes * PARTS.len()).div_ceil(max_lives as usize)
+        },
+        false => PARTS.len(),
+    };
+    let mut grid = [[' '; WIDTH]; HEIGHT];
+    for part in PARTS.iter().take(visible) {
+        for (line, column, c) in part.iter() {
+            grid[*line][*column] = *c;
+        }
+    }
+    grid.iter()
+        .map(|line| line.iter().collect::<String>().trim_end().to_string())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render, PARTS};
+
+    #[test]
+    fn test_render_bounds() {
+        assert!(render(7, 7).trim().is_empty());
+        // Each lost life has to add something to the drawing as long as there are enough parts
+        for max_lives in 1..=PARTS.len() as i32 {
+            let mut last = render(max_lives, max_lives);
+            for lives_left in (0..max_lives).rev() {
+                let current = render(lives_left, max_lives);
+                assert_ne!(current, last);
+                last = current;
+            }
+            assert_eq!(last, render(0, 11));
+        }
+    }
+}
@@ -1,8 +1,43 @@
-use std::collections::HashMap;
+use std::{collections::{BTreeMap, HashMap, VecDeque}, fmt::Display, sync::{atomic::{AtomicU64, Ordering}, Arc}, time::{Duration, Instant}};
 
 use uuid::Uuid;
 
-use super::{GameManager, MAX_LIVES};
+use super::{chat::{ChatError, ChatMessage, MAX_CHAT_HISTORY}, guess::Guess, series::{RematchError, Series, MAX_SERIES_LENGTH}, timer::Timer, turns::{TurnError, Turns}, GameManager, ALPHABET, MAX_LIVES};
+
+/// Time after which a spectator is not counted anymore when the game state has not been requested again
+pub const SPECTATOR_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Number of games that have been won and lost, shared by all games of a [GameManager](../struct.GameManager.html).
+///
+/// Every round that ends is counted, no matter if it ended by a guess, a time limit, a race or the admin api.
+#[derive(Default)]
+pub struct GameResults {
+    wins: AtomicU64,
+    losses: AtomicU64,
+}
+
+impl GameResults {
+    /// Counts a game that has ended
+    pub fn record(&self, won: bool) {
+        let counter = match won {
+            true => &self.wins,
+            false => &self.losses,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// # Returns
+    /// The number of games that have been won
+    pub fn wins(&self) -> u64 {
+        self.wins.load(Ordering::Relaxed)
+    }
+
+    /// # Returns
+    /// The number of games that have been lost
+    pub fn losses(&self) -> u64 {
+        self.losses.load(Ordering::Relaxed)
+    }
+}
 
 /// Representation of a game
 pub struct Game {
@@ -18,27 +53,117 @@ pub struct Game {
     game_id: Uuid,
     /// All letters that where guessed
     guessed_letters: Vec<Letter>,
+    /// All guesses in the order in which they were submitted
+    history: Vec<String>,
+    /// The time at which the game was created
+    created: Instant,
+    /// The order of the players when the game is turn-based
+    turns: Option<Turns>,
+    /// The player that created the game
+    host: Uuid,
+    /// `false` when the host has disabled spectating
+    spectating: bool,
+    /// The time at which each spectator requested the game state the last time
+    spectators: HashMap<Uuid, Instant>,
+    /// The last [MAX_CHAT_HISTORY](../chat/constant.MAX_CHAT_HISTORY.html) chat messages of the players
+    chat: VecDeque<ChatMessage>,
+    /// The results of the rounds that the players have played in this game
+    series: Series,
+    /// The time limits of the game
+    timer: Option<Timer>,
+    /// `true` when the player solves as many words as possible until the time of the game runs out
+    time_attack: bool,
+    /// `true` when the word is not chosen upfront but changed to evade the guesses, see [evil](#method.evil)
+    evil: bool,
+    /// Counts the result whenever the game ends, `None` when the game is not managed by a game manager
+    results: Option<Arc<GameResults>>,
 }
 
 impl Game {
     /// Construct a new game with a random word and an assigned player.
     pub fn new(game_manager: &GameManager, game_id: Uuid, player_id: Uuid) -> Self {
+        Self::with_word(game_id, player_id, &game_manager.random_word(), MAX_LIVES)
+    }
+
+    /// Construct a new game with the word and an assigned player.
+    /// 
+    /// This can be used to run a game without a [GameManager](../struct.GameManager.html), the word is expected to be
+    /// normalized with [normalize_word](../fn.normalize_word.html).
+    pub fn with_word(game_id: Uuid, player_id: Uuid, word: &str, lives: i32) -> Self {
         let mut guessed_letters = Vec::new();
-        for c in b'a'..=b'z' {
-            guessed_letters.push(Letter::new((c as char).to_uppercase().to_string().chars().next().unwrap()));
+        for c in ALPHABET.chars() {
+            guessed_letters.push(Letter::new(c));
         }
         let mut players = HashMap::new();
-        players.insert(player_id, Player::new(player_id));
+        players.insert(player_id, Player::new(player_id, 1));
         Self {
             players,
-            word: Word::new(&game_manager.random_word()),
+            word: Word::new(word),
             game_state: GameState::Running,
-            lives: MAX_LIVES,
+            lives,
             game_id,
             guessed_letters,
+            history: Vec::new(),
+            created: Instant::now(),
+            turns: None,
+            host: player_id,
+            spectating: true,
+            spectators: HashMap::new(),
+            chat: VecDeque::new(),
+            series: Series::new(None, true),
+            timer: None,
+            time_attack: false,
+            evil: false,
+            results: None,
         }
     }
 
+    /// Construct a new evil game in which the word is not fixed.
+    /// 
+    /// The game keeps all candidates that match the guesses so far. For each new letter the candidates are split into
+    /// families by the positions of the letter and the largest family is kept, so the letter is only revealed when
+    /// most candidates contain it. A guessed word is only correct when it is the last candidate left. The game string,
+    /// the lives and the results of the guesses are the same as in a normal game with the word that is left in the end.
+    /// 
+    /// `candidates` should only contain words with the same length and `-` at the same positions as `word`, which is
+    /// the word that is shown when no letter has been guessed yet.
+    pub fn evil(game_id: Uuid, player_id: Uuid, word: &str, candidates: Vec<String>) -> Self {
+        let mut game = Self::with_word(game_id, player_id, word, MAX_LIVES);
+        game.word = Word::evil(word, candidates);
+        game.evil = true;
+        game
+    }
+
+    /// Construct a new game like [with_word](#method.with_word) in which the time limits of the timer are enforced, see
+    /// [check_time](#method.check_time).
+    pub fn with_timer(game_id: Uuid, player_id: Uuid, word: &str, timer: Timer) -> Self {
+        let mut game = Self::with_word(game_id, player_id, word, MAX_LIVES);
+        game.timer = Some(timer);
+        game
+    }
+
+    /// Construct a new time-attack game that starts with the word and ends after the duration.
+    /// 
+    /// Each solved or lost word is counted in the [series](#method.series), the next word is started with
+    /// [next_word](#method.next_word). The game is won when at least one word has been solved in time.
+    pub fn time_attack(game_id: Uuid, player_id: Uuid, word: &str, duration: Duration) -> Self {
+        let mut game = Self::with_timer(game_id, player_id, word, Timer::new(None, Some(duration)));
+        game.time_attack = true;
+        game
+    }
+
+    /// Construct a new turn-based game in which the host chooses the first word.
+    /// 
+    /// Players can join until the host has chosen the word with [choose_word](#method.choose_word), see
+    /// [Turns](../turns/struct.Turns.html) for the order in which the players guess.
+    pub fn with_turns(game_id: Uuid, host_id: Uuid, timeout: Duration, dictionary: bool) -> Self {
+        let mut game = Self::with_word(game_id, host_id, "", MAX_LIVES);
+        game.game_state = GameState::Choosing;
+        game.turns = Some(Turns::new(host_id, timeout, dictionary));
+        game.series = Series::new(None, false);
+        game
+    }
+
     /// Returns the current word in the following formatting:
     /// 
     /// If no letters are guessed:   _____
@@ -78,6 +203,9 @@ impl Game {
     }
 
     /// Guesses a letter/word and returns a number to indicate that status
+    /// 
+    /// The guess has to be parsed into a [Guess](../guess/enum.Guess.html) first, invalid input is rejected there and
+    /// does not cost a life.
     /// # Returns
     /// `1` when the letter was correct and the word is guessed completely or when a correct word was guessed
     /// 
@@ -88,49 +216,375 @@ impl Game {
     /// `4` when letter/word was false and all lives are gone
     /// 
     /// '5' when the letter was already guessed
-    pub fn guess(&mut self, guess: String) -> i32 {
-        if guess.len() == 1 {
-            // User submitted a single letter
-            let c = guess.chars().next().unwrap().to_uppercase().to_string().chars().next().unwrap();
-            // Update guessed letters vector
-            if !self.add_letter_guessed(c) {
+    /// 
+    /// `6` when the game has already ended or the word has not been chosen yet, the game is not changed in that case
+    pub fn guess(&mut self, guess: Guess) -> i32 {
+        if !matches!(self.game_state, GameState::Running) {
+            return 6;
+        }
+        self.history.push(guess.to_string());
+        match guess {
+            Guess::Letter(c) => {
+                // Update guessed letters vector
+                if !self.add_letter_guessed(c) {
+                    self.guessed_letters.sort();
+                    return 5
+                } 
                 self.guessed_letters.sort();
-                return 5
-            } 
-            self.guessed_letters.sort();
-            // guess letters
-            let mut something_guessed = false;
-            for letter in &mut self.word.letters {
-                if letter.character == c {
-                    letter.guessed = true;
-                    something_guessed = true;
+                self.word.evade_letter(c);
+                // guess letters
+                let mut something_guessed = false;
+                for letter in &mut self.word.letters {
+                    if letter.character == c {
+                        letter.guessed = true;
+                        something_guessed = true;
+                    }
                 }
-            }
-            if something_guessed && !self.solved() {
-                return 2;
-            }
-        } else {
-            // User submitted a word
-            // Check if word is correct
-            if self.word.to_string() == guess.to_ascii_uppercase() {
-                self.word.set_solved();
-            }
+                if something_guessed && !self.solved() {
+                    return 2;
+                }
+            },
+            Guess::Word(word) => {
+                // Check if word is correct
+                self.word.evade_word(&word);
+                if self.word.to_string() == word {
+                    self.word.set_solved();
+                }
+            },
         }
         // check lives
         self.lives -= 1;
         if self.lives == 0 || self.solved() {
             if self.solved() {
                 self.lives += 1; //Increment lives to get the amount of lives that where left when the game was won
-                self.game_state = GameState::Done(true);
+                self.end_word(true);
                 return 1;
             } else if self.lives == 0 {
-                self.game_state = GameState::Done(false);
+                self.end_word(false);
                 return 4;
             }
         }
         3
     }
 
+    /// Guesses a letter/word like [guess](#method.guess) in the name of the player.
+    /// 
+    /// In turn-based games only the current player can guess and the turn is passed to the next player afterwards.
+    /// When the guess ends the round its result is added to the [series](#method.series).
+    /// # Returns
+    /// The result of [guess](#method.guess) or why the player can not guess now
+    pub fn guess_as(&mut self, player_id: Uuid, guess: Guess) -> Result<i32, TurnError> {
+        self.check_time();
+        if let Some(turns) = &mut self.turns {
+            match self.game_state {
+                GameState::Choosing => return Err(TurnError::WaitingForWord),
+                GameState::Running if turns.current() != player_id => return Err(TurnError::NotYourTurn),
+                GameState::Running => turns.next_turn(),
+                GameState::Done(_) => (),
+            }
+        }
+        let result = self.guess(guess);
+        if let Some(timer) = &mut self.timer {
+            timer.guessed();
+        }
+        match result {
+            1 => self.series.record(true, Some(player_id)),
+            4 => self.series.record(false, self.turns.as_ref().map(Turns::chooser)),
+            _ => (),
+        }
+        Ok(result)
+    }
+
+    /// Adds a player to a turn-based game, this is only possible between rounds.
+    pub fn add_player(&mut self, player_id: Uuid) -> Result<(), TurnError> {
+        let Some(turns) = &mut self.turns else {
+            return Err(TurnError::NotTurnBased);
+        };
+        if let GameState::Running = self.game_state {
+            return Err(TurnError::RoundRunning);
+        }
+        turns.add(player_id);
+        let number = self.players.values().map(|player| player.number).max().unwrap_or(0) + 1;
+        self.players.insert(player_id, Player::new(player_id, number));
+        Ok(())
+    }
+
+    /// Removes the player from the game, the other players keep playing.
+    /// 
+    /// In a turn-based game the player is removed from the order, a running round ends when the chooser leaves or less
+    /// than two players are left. When the host leaves the next player becomes the host.
+    pub fn remove_player(&mut self, player_id: Uuid) {
+        if self.players.remove(&player_id).is_none() {
+            return;
+        }
+        // The round can not go on without the player that knows the word
+        let chooser_left = self.turns.as_ref().map(|turns| turns.chooser() == player_id).unwrap_or(false);
+        if chooser_left && matches!(self.game_state, GameState::Running) {
+            self.force_end();
+        }
+        if let Some(turns) = &mut self.turns {
+            turns.remove(player_id);
+            if turns.players().len() < 2 && matches!(self.game_state, GameState::Running) {
+                self.force_end();
+            }
+        }
+        if self.host == player_id {
+            let next_host = match &self.turns {
+                Some(turns) => turns.players().first().copied(),
+                None => self.players.keys().next().copied(),
+            };
+            self.host = next_host.unwrap_or(player_id);
+        }
+    }
+
+    /// Starts the next round of a turn-based game with the word, the word is expected to be normalized with
+    /// [normalize_word](../fn.normalize_word.html).
+    /// 
+    /// The host chooses the word of the first round, afterwards the role is passed on to the next player in each round.
+    pub fn choose_word(&mut self, player_id: Uuid, word: &str) -> Result<(), TurnError> {
+        let Some(turns) = &mut self.turns else {
+            return Err(TurnError::NotTurnBased);
+        };
+        let chooser = match self.game_state {
+            GameState::Running => return Err(TurnError::RoundRunning),
+            GameState::Choosing => turns.chooser(),
+            GameState::Done(_) => turns.next_chooser(),
+        };
+        if chooser != player_id {
+            return Err(TurnError::NotChooser);
+        }
+        if turns.players().len() < 2 {
+            return Err(TurnError::NotEnoughPlayers);
+        }
+        turns.start_round();
+        self.start_round(word);
+        Ok(())
+    }
+
+    /// Starts a new round with the word for the same players once the game has ended, the game id stays the same.
+    /// 
+    /// The result of the previous round is kept in the [series](#method.series). When `best_of` is set a new series with
+    /// that many rounds is started, otherwise the current series is continued.
+    /// 
+    /// The time limits start again, a rematch of a time-attack game starts a new run in which no series can be played.
+    /// `candidates` are the possible words of an evil game and are ignored otherwise, see [evil](#method.evil).
+    pub fn rematch(&mut self, word: &str, candidates: Vec<String>, best_of: Option<u32>) -> Result<(), RematchError> {
+        if self.turns.is_some() {
+            return Err(RematchError::TurnBased);
+        }
+        if self.completed().is_none() {
+            return Err(RematchError::RoundRunning);
+        }
+        if let Some(best_of) = best_of {
+            if best_of == 0 || best_of > MAX_SERIES_LENGTH || self.time_attack {
+                return Err(RematchError::InvalidLength);
+            }
+            self.series = Series::new(Some(best_of), true);
+        }
+        if self.time_attack {
+            self.series = Series::new(None, true);
+        }
+        if let Some(timer) = &mut self.timer {
+            timer.restart();
+        }
+        self.start_round(word);
+        if self.evil {
+            self.word = Word::evil(word, candidates);
+        }
+        Ok(())
+    }
+
+    /// Starts the next word of a time-attack game after the previous word has been solved or lost.
+    /// 
+    /// When the time has run out the game ends instead.
+    pub fn next_word(&mut self, word: &str) {
+        if !self.time_attack || self.completed().is_none() {
+            return;
+        }
+        let Some(timer) = &mut self.timer else {
+            return;
+        };
+        if timer.game_expired(Instant::now()) {
+            self.end(self.series.solved() > 0);
+            return;
+        }
+        timer.guessed();
+        self.start_round(word);
+    }
+
+    /// Enforces the time limits of the game, this is checked lazily whenever the game is accessed.
+    /// 
+    /// Every time the time for a guess runs out a life is lost. When the time of the game runs out the game is lost,
+    /// a time-attack game is won when at least one word has been solved.
+    pub fn check_time(&mut self) {
+        self.check_time_at(Instant::now());
+    }
+
+    /// Enforces the time limits of the game like [check_time](#method.check_time) at the time `now`
+    pub fn check_time_at(&mut self, now: Instant) {
+        if !matches!(self.game_state, GameState::Running) {
+            return;
+        }
+        let Some(timer) = &mut self.timer else {
+            return;
+        };
+        if timer.game_expired(now) {
+            if self.time_attack {
+                self.end(self.series.solved() > 0);
+            } else {
+                self.series.record(false, None);
+                self.end(false);
+            }
+            return;
+        }
+        let expired = timer.expired_guesses(now) as i32;
+        if expired > 0 {
+            self.lives = (self.lives - expired).max(0);
+            if self.lives == 0 {
+                self.series.record(false, None);
+                self.end(false);
+            }
+        }
+    }
+
+    /// Counts the results of the game in `results` whenever the game ends from now on
+    pub fn count_results(&mut self, results: Arc<GameResults>) {
+        self.results = Some(results);
+    }
+
+    /// Ends the game with the result and counts it
+    fn end(&mut self, won: bool) {
+        self.game_state = GameState::Done(won);
+        if let Some(results) = &self.results {
+            results.record(won);
+        }
+    }
+
+    /// Ends the word that is guessed. A time-attack game continues with the next word, so only the word ends and the
+    /// game is counted once its time has run out.
+    fn end_word(&mut self, won: bool) {
+        match self.time_attack {
+            true => self.game_state = GameState::Done(won),
+            false => self.end(won),
+        }
+    }
+
+    /// # Returns
+    /// The time limits of the game
+    pub fn timer(&self) -> Option<&Timer> {
+        self.timer.as_ref()
+    }
+
+    /// # Returns
+    /// `true` when this is a time-attack game
+    pub fn is_time_attack(&self) -> bool {
+        self.time_attack
+    }
+
+    /// # Returns
+    /// `true` when the word of this game changes to evade the guesses
+    pub fn is_evil(&self) -> bool {
+        self.evil
+    }
+
+    /// # Returns
+    /// The results of the rounds that have been played in this game
+    pub fn series(&self) -> &Series {
+        &self.series
+    }
+
+    /// Resets the game for a new round with the word, a decided series is started again with the same length
+    fn start_round(&mut self, word: &str) {
+        if self.series.finished() {
+            self.series = Series::new(self.series.best_of(), self.turns.is_none());
+        }
+        self.word = Word::new(word);
+        self.lives = MAX_LIVES;
+        self.guessed_letters.iter_mut().for_each(|letter| letter.guessed = false);
+        self.history.clear();
+        self.game_state = GameState::Running;
+    }
+
+    /// # Returns
+    /// The order of the players when the game is turn-based
+    pub fn turns(&self) -> Option<&Turns> {
+        self.turns.as_ref()
+    }
+
+    /// # Returns
+    /// The player that has to choose the next word of a turn-based game or `None` while a round is running
+    pub fn next_word_chooser(&self) -> Option<Uuid> {
+        let turns = self.turns.as_ref()?;
+        match self.game_state {
+            GameState::Running => None,
+            GameState::Choosing => Some(turns.chooser()),
+            GameState::Done(_) => Some(turns.next_chooser()),
+        }
+    }
+
+    /// # Returns
+    /// The player that created the game
+    pub fn host(&self) -> Uuid {
+        self.host
+    }
+
+    /// # Returns
+    /// `true` when spectators can watch the game
+    pub fn spectating(&self) -> bool {
+        self.spectating
+    }
+
+    /// Allows or forbids spectators to watch the game, the current spectators are removed when spectating is disabled.
+    pub fn set_spectating(&mut self, enabled: bool) {
+        self.spectating = enabled;
+        if !enabled {
+            self.spectators.clear();
+        }
+    }
+
+    /// Records that the spectator has requested the game state
+    pub fn watch(&mut self, spectator_id: Uuid) {
+        self.spectators.retain(|_, seen| seen.elapsed() < SPECTATOR_TIMEOUT);
+        self.spectators.insert(spectator_id, Instant::now());
+    }
+
+    /// # Returns
+    /// The number of spectators that have requested the game state within the [SPECTATOR_TIMEOUT]
+    pub fn spectators(&self) -> usize {
+        self.spectators.values().filter(|seen| seen.elapsed() < SPECTATOR_TIMEOUT).count()
+    }
+
+    /// Adds the chat message of the player to the game, the oldest message is removed when the history is full.
+    /// 
+    /// Messages that contain the word are rejected while the round is running, because a player may already know it,
+    /// for example the chooser of a turn-based game or a player that has solved the word.
+    /// # Returns
+    /// The message that was added or why it was rejected
+    pub fn post_message(&mut self, player_id: Uuid, text: &str) -> Result<ChatMessage, ChatError> {
+        let message = ChatMessage::new(player_id, text)?;
+        if matches!(self.game_state, GameState::Running) && message.reveals(&self.word.to_string()) {
+            return Err(ChatError::RevealsWord);
+        }
+        if self.chat.len() >= MAX_CHAT_HISTORY {
+            self.chat.pop_front();
+        }
+        self.chat.push_back(message.clone());
+        Ok(message)
+    }
+
+    /// # Returns
+    /// The chat messages, the oldest message comes first
+    pub fn chat(&self) -> &VecDeque<ChatMessage> {
+        &self.chat
+    }
+
+    /// # Returns
+    /// The number of the player starting at 1 in the order in which the players joined, `0` when the player is not
+    /// assigned to this game. The number stays the same when other players leave.
+    pub fn player_number(&self, player_id: Uuid) -> usize {
+        self.players.get(&player_id).map(|player| player.number).unwrap_or(0)
+    }
+
     /// Adds the input letter to the list of guessed characters
     /// # Returns
     /// - `true` letter was not yet guessed
@@ -199,6 +653,68 @@ impl Game {
         self.game_id
     }
 
+    /// # Returns
+    /// The ids of all players that are assigned to this game
+    pub fn player_ids(&self) -> Vec<Uuid> {
+        self.players.keys().copied().collect()
+    }
+
+    /// # Returns
+    /// All guesses in the order in which they were submitted
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// # Returns
+    /// How long ago the game was created
+    pub fn age(&self) -> Duration {
+        self.created.elapsed()
+    }
+
+    /// # Returns
+    /// The word that should be guessed, independent of the state of the game.
+    /// 
+    /// Should not be shown to players, use [word](#method.word) instead.
+    pub fn solution(&self) -> String {
+        self.word.to_string()
+    }
+
+    /// Ends the game immediately, the game is marked as lost and a running round is counted as lost in the series.
+    pub fn force_end(&mut self) {
+        if let GameState::Running = self.game_state {
+            self.series.record(false, self.turns.as_ref().map(Turns::chooser));
+        }
+        if self.completed().is_none() {
+            self.end(false);
+        }
+    }
+
+    /// # Returns
+    /// The number of letters that have been revealed and the number of letters of the word, `-` is not counted
+    pub fn progress(&self) -> (usize, usize) {
+        let letters = self.word.letters.iter().filter(|letter| letter.character != '-');
+        let (revealed, total) = letters.fold((0, 0), |(revealed, total), letter| (revealed + usize::from(letter.guessed), total + 1));
+        (revealed, total)
+    }
+
+    /// # Returns
+    /// All players that are assigned to this game
+    pub fn players(&self) -> Vec<&Player> {
+        self.players.values().collect()
+    }
+
+    /// # Returns
+    /// `playing` while the game is running, `won` or `lost` when the game has been completed and `choosing` while a
+    /// turn-based game waits for the word
+    pub fn status(&self) -> &'static str {
+        match self.game_state {
+            GameState::Done(true) => "won",
+            GameState::Done(false) => "lost",
+            GameState::Running => "playing",
+            GameState::Choosing => "choosing",
+        }
+    }
+
     /// Returns the names of the teammates of the player with the id
     pub fn teammates(&self, player_id: Uuid) -> String {
         let mut s = String::new();
@@ -237,6 +753,8 @@ impl Game {
 enum GameState {
     /// Symbolizes that the game is running.
     Running,
+    /// Symbolizes that a turn-based game waits for the word of the next round.
+    Choosing,
     /// Symbolizes that this game is over. 
     /// 
     /// Boolean value determines if the game was won (`true`) or lost (`false`).
@@ -250,14 +768,17 @@ pub struct Player {
     pub id: Uuid,
     /// The name of the player (currently not used)
     pub name: String,
+    /// Identifies the player to the other players of the game, see [player_number](struct.Game.html#method.player_number)
+    pub number: usize,
 }
 
 impl Player {
     /// Create a new player
-    pub fn new(id: Uuid) -> Self {
+    pub fn new(id: Uuid, number: usize) -> Self {
         Self { 
             id,
             name: String::from("steve"),
+            number,
         }
     }
 }
@@ -265,6 +786,9 @@ impl Player {
 /// Word that should be guessed
 struct Word {
     pub letters: Vec<Letter>,
+    /// The words that are still possible in an evil game, the letters show the first candidate.
+    /// Empty when the word is fixed.
+    candidates: Vec<String>,
 }
 
 impl Word {
@@ -277,7 +801,57 @@ impl Word {
             letters.push(Letter::new(c));
         }
         Self { 
-            letters 
+            letters,
+            candidates: Vec::new(),
+        }
+    }
+
+    /// Create a word that is not fixed but can become any of the candidates
+    fn evil(word: &str, mut candidates: Vec<String>) -> Self {
+        candidates.retain(|candidate| candidate != word);
+        candidates.insert(0, word.to_string());
+        Self {
+            candidates,
+            ..Self::new(word)
+        }
+    }
+
+    /// Keeps the largest family of candidates that have the letter at the same positions.
+    /// 
+    /// When several families have the same size the family in which the letter is revealed the least is kept.
+    fn evade_letter(&mut self, c: char) {
+        if self.candidates.len() < 2 {
+            return;
+        }
+        let mut families: BTreeMap<Vec<usize>, Vec<String>> = BTreeMap::new();
+        for candidate in self.candidates.drain(..) {
+            let positions = candidate.chars().enumerate().filter(|(_, letter)| *letter == c).map(|(index, _)| index).collect();
+            families.entry(positions).or_default().push(candidate);
+        }
+        let family = families.into_iter()
+            .max_by(|(a_positions, a), (b_positions, b)| a.len().cmp(&b.len()).then(b_positions.len().cmp(&a_positions.len())))
+            .map(|(_, family)| family)
+            .unwrap_or_default();
+        self.candidates = family;
+        self.show_first_candidate();
+    }
+
+    /// Removes the guessed word from the candidates while other candidates are left
+    fn evade_word(&mut self, word: &str) {
+        if self.candidates.len() < 2 {
+            return;
+        }
+        self.candidates.retain(|candidate| candidate != word);
+        self.show_first_candidate();
+    }
+
+    /// Replaces the letters with the first candidate, the letters that have been revealed stay the same
+    fn show_first_candidate(&mut self) {
+        let Some(candidate) = self.candidates.first() else {
+            return;
+        };
+        for (letter, character) in self.letters.iter_mut().zip(candidate.chars()) {
+            letter.character = character;
         }
     }
 
@@ -289,14 +863,14 @@ impl Word {
     }
 }
 
-impl ToString for Word {
-    /// Returns the word
-    fn to_string(&self) -> String {
+impl Display for Word {
+    /// Writes the word
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut s = String::new();
         for l in &self.letters {
             s.push(l.character);
         }
-        s
+        write!(f, "{}", s)
     }
 }
 
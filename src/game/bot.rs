@@ -0,0 +1,132 @@
+use std::{fmt::Display, sync::Arc, time::{Duration, Instant}};
+
+use uuid::Uuid;
+
+use super::{guess::Guess, race::RaceError, solver::Solver, turns::TurnError, word_list::WordList};
+
+/// Time a bot waits between two moves, so that the other players can follow the game
+pub const BOT_DELAY: Duration = Duration::from_secs(2);
+
+/// The maximum number of bots in a race or a turn-based game
+pub const MAX_BOTS: usize = 4;
+
+/// Reasons why a bot can not join
+#[derive(Debug, PartialEq, Eq)]
+pub enum BotError {
+    /// The strength is not one of the [BOT_STRENGTHS](../solver/constant.BOT_STRENGTHS.html)
+    InvalidStrength,
+    /// The race or game already has [MAX_BOTS] bots
+    TooManyBots,
+    /// The race can not be joined
+    Race(RaceError),
+    /// The turn-based game can not be joined
+    Turn(TurnError),
+}
+
+impl Display for BotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BotError::InvalidStrength => write!(f, "The strength of a bot has to be between 1 and 10"),
+            BotError::TooManyBots => write!(f, "At most {} bots can join", MAX_BOTS),
+            BotError::Race(err) => write!(f, "{}", err),
+            BotError::Turn(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+/// Computer player that guesses with the [Solver](../solver/struct.Solver.html).
+///
+/// In a race the bot plays its own game, in a turn-based game it guesses when it is its turn and chooses a random word
+/// when it is the chooser.
+pub struct Bot {
+    /// The game in which the bot plays
+    pub game_id: Uuid,
+    /// The race the bot has joined, `None` in turn-based games
+    pub race_id: Option<Uuid>,
+    /// Chance out of 10 that the bot chooses the best guess
+    pub strength: u8,
+    /// The time after which the bot makes its next move
+    next_move: Instant,
+}
+
+impl Bot {
+    pub fn new(game_id: Uuid, race_id: Option<Uuid>, strength: u8) -> Self {
+        Self {
+            game_id,
+            race_id,
+            strength,
+            next_move: Instant::now() + BOT_DELAY,
+        }
+    }
+
+    /// # Returns
+    /// `true` when the bot has waited long enough for its next move
+    pub fn ready(&self, now: Instant) -> bool {
+        now >= self.next_move
+    }
+
+    /// Schedules the next move of the bot when it was [ready](#method.ready) at `now`
+    pub fn moved(&mut self, now: Instant) {
+        if self.ready(now) {
+            self.next_move = now + BOT_DELAY;
+        }
+    }
+}
+
+/// Move of a bot that is submitted to its game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BotMove {
+    /// Choose the word of the next round of a turn-based game
+    Choose(String),
+    /// Guess in the game
+    Guess(Guess),
+}
+
+/// A bot that is ready to move together with the state of its game at the time the turn was collected.
+///
+/// The turns are collected while the game manager is only read, the [Solver] runs in [play](#method.play) without
+/// holding the lock.
+pub struct BotTurn {
+    /// The player id of the bot
+    pub player_id: Uuid,
+    task: Task,
+}
+
+/// What a bot does in its turn
+enum Task {
+    /// Choose the word that has already been drawn from the words file
+    Choose(String),
+    /// Guess with the solver
+    Guess {
+        strength: u8,
+        game_string: String,
+        guessed: Vec<char>,
+        words: Arc<WordList>,
+    },
+}
+
+impl BotTurn {
+    /// A turn in which the bot chooses the word
+    pub fn choose(player_id: Uuid, word: String) -> Self {
+        Self { player_id, task: Task::Choose(word) }
+    }
+
+    /// A turn in which the bot guesses in the game with the `game_string` and the `guessed` letters
+    pub fn guess(player_id: Uuid, strength: u8, game_string: String, guessed: Vec<char>, words: Arc<WordList>) -> Self {
+        Self { player_id, task: Task::Guess { strength, game_string, guessed, words } }
+    }
+
+    /// Computes the move of the bot
+    /// # Returns
+    /// The player id of the bot and its move, `None` when no letter is left to guess
+    pub fn play(self) -> Option<(Uuid, BotMove)> {
+        let bot_move = match self.task {
+            Task::Choose(word) => BotMove::Choose(word),
+            Task::Guess { strength, game_string, guessed, words } => {
+                let solver = Solver::new(words.words(), &game_string, &guessed);
+                BotMove::Guess(solver.guess_with_strength(strength, &mut rand::thread_rng())?)
+            },
+        };
+        Some((self.player_id, bot_move))
+    }
+}
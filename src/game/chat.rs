@@ -0,0 +1,87 @@
+use std::{fmt::Display, time::SystemTime};
+
+use uuid::Uuid;
+
+use super::normalize_word;
+
+/// The maximum number of characters of a chat message
+pub const MAX_CHAT_LENGTH: usize = 200;
+
+/// The number of chat messages that are kept per game, older messages are removed
+pub const MAX_CHAT_HISTORY: usize = 50;
+
+/// Message that a player has sent to the other players of the game
+#[derive(Clone, Debug)]
+pub struct ChatMessage {
+    /// The player that sent the message
+    pub player_id: Uuid,
+    /// The text of the message without surrounding whitespace
+    pub text: String,
+    /// The time at which the message was sent
+    pub sent: SystemTime,
+}
+
+/// Reasons why a chat message is rejected
+#[derive(Debug, PartialEq, Eq)]
+pub enum ChatError {
+    /// The message is empty or only contains whitespace
+    Empty,
+    /// The message is longer than [MAX_CHAT_LENGTH]
+    TooLong,
+    /// The message contains the word while the round is running
+    RevealsWord,
+}
+
+impl Display for ChatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChatError::Empty => write!(f, "The message is empty"),
+            ChatError::TooLong => write!(f, "The message is longer than {} characters", MAX_CHAT_LENGTH),
+            ChatError::RevealsWord => write!(f, "The message reveals the word"),
+        }
+    }
+}
+
+impl ChatMessage {
+    /// Validates the text of a message
+    pub fn new(player_id: Uuid, text: &str) -> Result<Self, ChatError> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(ChatError::Empty);
+        }
+        if text.chars().count() > MAX_CHAT_LENGTH {
+            return Err(ChatError::TooLong);
+        }
+        Ok(Self {
+            player_id,
+            text: text.to_string(),
+            sent: SystemTime::now(),
+        })
+    }
+
+    /// # Returns
+    /// `true` when the message contains the word, letters that are separated by spaces or other characters are also detected
+    pub fn reveals(&self, word: &str) -> bool {
+        let letters = |text: &str| normalize_word(text).chars().filter(|c| c.is_alphabetic()).collect::<String>();
+        let word = letters(word);
+        !word.is_empty() && letters(&self.text).contains(&word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::{ChatError, ChatMessage, MAX_CHAT_LENGTH};
+
+    #[test]
+    fn test_message() {
+        let player_id = Uuid::new_v4();
+        assert_eq!(ChatMessage::new(player_id, "  ").unwrap_err(), ChatError::Empty);
+        assert_eq!(ChatMessage::new(player_id, &"a".repeat(MAX_CHAT_LENGTH + 1)).unwrap_err(), ChatError::TooLong);
+        assert_eq!(ChatMessage::new(player_id, " Try E! ").unwrap().text, "Try E!");
+        assert!(ChatMessage::new(player_id, "it is b r ü c k e").unwrap().reveals("BRUECKE"));
+        assert!(ChatMessage::new(player_id, "Fluss ufer?").unwrap().reveals("FLUSS-UFER"));
+        assert!(!ChatMessage::new(player_id, "Brücken").unwrap().reveals("AUTO"));
+    }
+}
@@ -0,0 +1,106 @@
+use std::{fmt::Display, str::FromStr};
+
+use super::{normalize_word, ALPHABET};
+
+/// The maximum number of characters a guess can have
+pub const MAX_GUESS_LENGTH: usize = 64;
+
+/// Names of the guess outcomes, the index is the result of [Game::guess](../base_game/struct.Game.html#method.guess) minus one
+pub const GUESS_OUTCOMES: [&str; 6] = ["solved", "correct", "wrong", "lost", "repeated", "ended"];
+
+/// # Returns
+/// The name of the result of [Game::guess](../base_game/struct.Game.html#method.guess), see [GUESS_OUTCOMES]
+pub fn outcome_name(result: i32) -> &'static str {
+    usize::try_from(result - 1).ok().and_then(|index| GUESS_OUTCOMES.get(index)).copied().unwrap_or("unknown")
+}
+
+/// A validated guess that can be submitted with [Game::guess](../base_game/struct.Game.html#method.guess)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Guess {
+    /// A single letter of the [ALPHABET](../constant.ALPHABET.html)
+    Letter(char),
+    /// A whole word, normalized with [normalize_word](../fn.normalize_word.html)
+    Word(String),
+}
+
+/// Reasons why a guess is rejected, a rejected guess does not cost a life
+#[derive(Debug, PartialEq, Eq)]
+pub enum GuessError {
+    /// The guess is empty or only contains whitespace
+    Empty,
+    /// The guess contains a character that can not be part of a word
+    InvalidCharacter(char),
+    /// The guess is longer than [MAX_GUESS_LENGTH](constant.MAX_GUESS_LENGTH.html)
+    TooLong,
+}
+
+impl Display for GuessError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GuessError::Empty => write!(f, "The guess is empty"),
+            GuessError::InvalidCharacter(c) => write!(f, "The guess contains the invalid character {:?}", c),
+            GuessError::TooLong => write!(f, "The guess is longer than {} characters", MAX_GUESS_LENGTH),
+        }
+    }
+}
+
+impl FromStr for Guess {
+    type Err = GuessError;
+
+    /// Parses the input of a player.
+    ///
+    /// Surrounding whitespace is ignored. A single character is a letter, everything else is a word. Letters have to be
+    /// contained in the alphabet, so umlauts can only be guessed as part of a word where they are normalized.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let input = s.trim();
+        let mut chars = input.chars();
+        match (chars.next(), chars.next()) {
+            (None, _) => Err(GuessError::Empty),
+            (Some(c), None) => {
+                let mut upper = c.to_uppercase();
+                match (upper.next(), upper.next()) {
+                    (Some(letter), None) if ALPHABET.contains(letter) => Ok(Guess::Letter(letter)),
+                    _ => Err(GuessError::InvalidCharacter(c)),
+                }
+            },
+            _ => {
+                if input.chars().count() > MAX_GUESS_LENGTH {
+                    return Err(GuessError::TooLong);
+                }
+                if let Some(c) = input.chars().find(|c| !c.is_alphabetic() && *c != '-') {
+                    return Err(GuessError::InvalidCharacter(c));
+                }
+                let word = normalize_word(input);
+                match word.chars().find(|c| !ALPHABET.contains(*c) && *c != '-') {
+                    Some(c) => Err(GuessError::InvalidCharacter(c)),
+                    None => Ok(Guess::Word(word)),
+                }
+            },
+        }
+    }
+}
+
+impl Display for Guess {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Guess::Letter(c) => write!(f, "{}", c),
+            Guess::Word(word) => write!(f, "{}", word),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Guess, GuessError, MAX_GUESS_LENGTH};
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(" e ".parse(), Ok(Guess::Letter('E')));
+        assert_eq!("Brücke".parse(), Ok(Guess::Word(String::from("BRUECKE"))));
+        assert_eq!("".parse::<Guess>(), Err(GuessError::Empty));
+        assert_eq!("Ä".parse::<Guess>(), Err(GuessError::InvalidCharacter('Ä')));
+        assert_eq!("1".parse::<Guess>(), Err(GuessError::InvalidCharacter('1')));
+        assert_eq!("Auto!".parse::<Guess>(), Err(GuessError::InvalidCharacter('!')));
+        assert_eq!("A".repeat(MAX_GUESS_LENGTH + 1).parse::<Guess>(), Err(GuessError::TooLong));
+    }
+}
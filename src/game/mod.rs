@@ -1,89 +1,462 @@
-use std::{fs, collections::{HashMap, HashSet, LinkedList}};
-use rand::Rng;
+use std::{fmt::Display, net::IpAddr, sync::Arc, collections::{HashMap, HashSet, LinkedList}, time::{Duration, Instant}};
 use uuid::Uuid;
 
-use self::base_game::Game;
+use self::{base_game::{Game, GameResults}, bot::{Bot, BotError, BotMove, BotTurn, MAX_BOTS}, guess::Guess, race::{Race, RaceError, RaceState}, series::RematchError, solver::BOT_STRENGTHS, timer::Timer, turns::TurnError, word_list::{WordListError, WordSource}};
 
 /// Contains all base components that are required to run a game
 pub mod base_game;
+/// Computer players that join races and turn-based games
+pub mod bot;
+/// Chat messages of the players of a game
+pub mod chat;
+/// Parsing and validation of the guesses of the players
+pub mod guess;
+/// Races in which several players guess the same word
+pub mod race;
+/// Results of the rounds that the players of a game play in a row
+pub mod series;
+/// Ranks the next guesses by the information they reveal, used for hints, bots and benchmarks
+pub mod solver;
+/// Time limits of games
+pub mod timer;
+/// Order of the players in turn-based games
+pub mod turns;
+/// Loading and validation of words files
+pub mod word_list;
+
+/// All letters that can be guessed. Words may only contain these letters and `-`.
+pub const ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
 
 /// Determines how many lives players have when playing a game.
 /// 
-/// Should not be set higher than 10 because images will fail to load.
-const MAX_LIVES: i32 = 7;
+/// The hangman drawing in the frontend is spread over all lives, so any positive number can be used.
+pub const MAX_LIVES: i32 = 7;
 
 /// The maximum amount of active games at the same time.
 /// 
-/// If this number is reached the oldest game is deleted to make space for a new game.
-const MAX_ACTIVE_GAMES: usize = 1000;
+/// If this number is reached the oldest game that has ended is deleted to make space for a new game. Running games are
+/// never deleted, new games are rejected while all games are still running.
+pub const MAX_ACTIVE_GAMES: usize = 1000;
 
 /// Used to manage all currently running games.
 /// 
 /// One `GameManager` instance is managed by rocket and given to each request handler.
 pub struct GameManager {
     /// Contains all games that are currently running.
-    /// The key is the user id and the value is the game where the user is assigned to.
+    /// The key is the game id and the value is the game.
     games: HashMap<Uuid, Game>,// TODO replace Game with RwLock<Game>
-    /// All words from which a random word can be chosen for a game
-    words: Vec<String>,
+    /// Maps the player ids to the id of the game the player is assigned to.
+    players: HashMap<Uuid, Uuid>,
+    /// All words from which a random word can be chosen for a game.
+    /// 
+    /// The words are shared with the words watcher that reloads them when the words file changes.
+    words: Arc<WordSource>,
     /// All player ids that are already in use. 
     /// 
     /// A player id uniquely identifies the given player. 
     /// 
     /// It is also used to authorize the player against the server.
     player_ids: HashSet<Uuid>,
-    /// This list is used to remove the oldest uuid once the [MAX_ACTIVE_GAMES](constant.MAX_ACTIVE_GAMES.html) limit is reached
+    /// This list is used to remove the oldest ended game once the [MAX_ACTIVE_GAMES](constant.MAX_ACTIVE_GAMES.html) limit is reached
     /// and a new game is registered.
-    player_id_history: LinkedList<Uuid>,
+    game_id_history: LinkedList<Uuid>,
     /// All game ids that are currently in use.
     game_ids: HashSet<Uuid>,
+    /// The address of the client that registered the game, used to limit the number of games per client.
+    /// 
+    /// Games of clients with an unknown address are stored with `None` and share one limit.
+    clients: HashMap<Uuid, Option<IpAddr>>,
+    /// All races, the key is the id of the race.
+    races: HashMap<Uuid, Race>,
+    /// Maps the ids of the games that are part of a race to the id of the race.
+    race_games: HashMap<Uuid, Uuid>,
+    /// The bots that play in the games by their player id
+    bots: HashMap<Uuid, Bot>,
+    /// The number of games that have been won and lost
+    results: Arc<GameResults>,
 }
 
 impl GameManager {
-    /// Create a new `GameManager`
+    /// Create a new `GameManager` with the words from `words.txt`
     pub fn new() -> Self {
-        let file = fs::read_to_string("words.txt").expect("Unable to read words file!");
-        let words: Vec<String> = file.split('\n').map(|s| String::from(s).to_uppercase()).collect();    
+        Self::with_words(WordSource::load("words.txt").expect("Unable to read words file!"))
+    }
+
+    /// Create a new `GameManager` that chooses the words of new games from `words`
+    pub fn with_words(words: WordSource) -> Self {
+        let words = Arc::new(words);
         Self {
             games: HashMap::new(),
+            players: HashMap::new(),
             words,
             player_ids: HashSet::new(), 
-            player_id_history: LinkedList::new(),
+            game_id_history: LinkedList::new(),
             game_ids: HashSet::new(),
+            clients: HashMap::new(),
+            races: HashMap::new(),
+            race_games: HashMap::new(),
+            bots: HashMap::new(),
+            results: Arc::new(GameResults::default()),
         }
     }
 
-    /// Registers a new game
+    /// Registers a new game that is not counted against a client
     /// # Returns
-    /// [RegisterResult](struct.RegisterResult.html) the result of the registration
-    pub fn register_game(&mut self) -> RegisterResult {
+    /// `Ok(RegisterResult)` the result of the registration
+    /// 
+    /// `Err(RegisterError)` when the [MAX_ACTIVE_GAMES](constant.MAX_ACTIVE_GAMES.html) limit is reached
+    pub fn register_game(&mut self) -> Result<RegisterResult, RegisterError> {
+        let word = self.random_word();
+        self.insert_game(|game_id, player_id| Game::with_word(game_id, player_id, &word, MAX_LIVES))
+    }
+
+    /// Registers the game that is constructed by `create` from the new game and player id.
+    /// 
+    /// When the [MAX_ACTIVE_GAMES](constant.MAX_ACTIVE_GAMES.html) limit is reached the oldest game that has ended is
+    /// deleted, the game is rejected when all games are still running.
+    fn insert_game(&mut self, create: impl FnOnce(Uuid, Uuid) -> Game) -> Result<RegisterResult, RegisterError> {
+        let mut evicted = false;
+        if self.games.len() >= MAX_ACTIVE_GAMES {
+            // Turn-based games wait for the next word once a round has ended, so only other games are finished
+            let ended = self.game_id_history.iter()
+                .find(|id| self.games.get(id).map(|game| game.completed().is_some() && game.turns().is_none()).unwrap_or(false))
+                .copied()
+                .ok_or(RegisterError::GameLimit)?;
+            evicted = self.delete_game_by_id(ended);
+        }
         let game_id = self.free_game_id();
         let player_id = self.free_player_id();
-        let game = Game::new(self, game_id, player_id);
-        self.player_id_history.push_back(player_id);
-        // Verify active game limit
-        if self.player_id_history.len() > MAX_ACTIVE_GAMES {
-            // Game limit is reached
-            let player_id_to_delete = self.player_id_history.pop_front().unwrap();
-            self.delete_game(player_id_to_delete);
+        let mut game = create(game_id, player_id);
+        game.count_results(Arc::clone(&self.results));
+        self.game_id_history.push_back(game_id);
+        self.games.insert(game_id, game);
+        self.players.insert(player_id, game_id);
+        Ok(RegisterResult {player_id, game_id, evicted})
+    }
+
+    /// Registers a new game for the client.
+    /// 
+    /// A client can have at most `max_games` games at the same time, this way a single client can not fill the
+    /// [MAX_ACTIVE_GAMES](constant.MAX_ACTIVE_GAMES.html) limit by registering a lot of games. `0` disables the limit.
+    /// All clients with an unknown address share one limit.
+    /// # Returns
+    /// `Ok(RegisterResult)` the result of the registration
+    /// 
+    /// `Err(RegisterError)` when the client already has `max_games` games or the game limit is reached
+    pub fn register_game_for(&mut self, client: Option<IpAddr>, max_games: usize) -> Result<RegisterResult, RegisterError> {
+        let word = self.random_word();
+        self.insert_game_for(client, max_games, |game_id, player_id| Game::with_word(game_id, player_id, &word, MAX_LIVES))
+    }
+
+    /// Registers the game that is constructed by `create`, the client is limited like in [register_game_for](#method.register_game_for).
+    fn insert_game_for(&mut self, client: Option<IpAddr>, max_games: usize, create: impl FnOnce(Uuid, Uuid) -> Game) -> Result<RegisterResult, RegisterError> {
+        if max_games > 0 && self.games_of_client(client) >= max_games {
+            return Err(RegisterError::ClientLimit);
         }
-        self.games.insert(player_id, game);
-        RegisterResult {player_id}
+        let result = self.insert_game(create)?;
+        self.clients.insert(result.game_id, client);
+        Ok(result)
     }
 
-    /// Reads the words file and returns a random word
-    fn random_word(&self) -> String {
-        let number = rand::thread_rng().gen_range(0..self.words.len());
-        let mut transformed_word = String::new();
-        for c in self.words[number].clone().chars() {
-            match c {
-                'Ä' => transformed_word.push_str("AE"),
-                'Ö' => transformed_word.push_str("OE"),
-                'Ü' => transformed_word.push_str("UE"),
-                _ => transformed_word.push(c),
+    /// Registers a new game with a random word in which the time limits of the timer are enforced.
+    /// 
+    /// The client is limited like in [register_game_for](#method.register_game_for).
+    /// # Returns
+    /// The result of the registration or why the game is rejected, see [RegisterError]
+    pub fn register_timed_game(&mut self, client: Option<IpAddr>, max_games: usize, timer: Timer) -> Result<RegisterResult, RegisterError> {
+        let word = self.random_word();
+        self.insert_game_for(client, max_games, |game_id, player_id| Game::with_timer(game_id, player_id, &word, timer))
+    }
+
+    /// Registers a new time-attack game in which the player solves as many words as possible within the duration.
+    /// 
+    /// The client is limited like in [register_game_for](#method.register_game_for).
+    /// # Returns
+    /// The result of the registration or why the game is rejected, see [RegisterError]
+    pub fn register_time_attack(&mut self, client: Option<IpAddr>, max_games: usize, duration: Duration) -> Result<RegisterResult, RegisterError> {
+        let word = self.random_word();
+        self.insert_game_for(client, max_games, |game_id, player_id| Game::time_attack(game_id, player_id, &word, duration))
+    }
+
+    /// Creates a new race with a random word and registers the first player to it.
+    /// 
+    /// The client is limited like in [register_game_for](#method.register_game_for).
+    /// # Returns
+    /// The id of the race and the result of the registration or why the game is rejected, see [RegisterError]
+    pub fn create_race(&mut self, client: Option<IpAddr>, max_games: usize) -> Result<(Uuid, RegisterResult), RegisterError> {
+        let word = self.random_word();
+        let result = self.insert_game_for(client, max_games, |game_id, player_id| Game::with_word(game_id, player_id, &word, MAX_LIVES))?;
+        let mut race_id = Uuid::new_v4();
+        while self.races.contains_key(&race_id) {
+            race_id = Uuid::new_v4();
+        }
+        self.races.insert(race_id, Race::new(race_id, word, result.game_id));
+        self.race_games.insert(result.game_id, race_id);
+        Ok((race_id, result))
+    }
+
+    /// Registers a new player to the race, the player gets an own game with the word of the race.
+    /// 
+    /// The client is limited like in [register_game_for](#method.register_game_for).
+    /// # Returns
+    /// The result of the registration or why the player can not join
+    pub fn join_race(&mut self, race_id: Uuid, client: Option<IpAddr>, max_games: usize) -> Result<RegisterResult, RaceError> {
+        let race = self.races.get(&race_id).ok_or(RaceError::NotFound)?;
+        if race.finished(&self.games) {
+            return Err(RaceError::Finished);
+        }
+        let word = race.word().to_string();
+        let result = self.insert_game_for(client, max_games, |game_id, player_id| Game::with_word(game_id, player_id, &word, MAX_LIVES))
+            .map_err(RaceError::Register)?;
+        // The race is deleted when the registration had to delete the last game of the race to make space
+        let race = self.races.get_mut(&race_id).ok_or(RaceError::NotFound)?;
+        race.add_game(result.game_id);
+        self.race_games.insert(result.game_id, race_id);
+        Ok(result)
+    }
+
+    /// # Returns
+    /// 
+    /// `Some(RaceState)` the state of the race in which the game is played
+    /// 
+    /// `None` when the game is not part of a race
+    pub fn race_of_game(&self, game_id: Uuid) -> Option<RaceState> {
+        let race = self.races.get(self.race_games.get(&game_id)?)?;
+        Some(race.state(&self.games))
+    }
+
+    /// Submits the guess of the player to the game with the id and updates the race the game is part of.
+    /// 
+    /// A time-attack game continues with a new word once the word has been solved or lost.
+    /// # Returns
+    /// `Ok(i32)` the result of [Game::guess](base_game/struct.Game.html#method.guess)
+    /// 
+    /// `Err(TurnError)` when no game with the id exists or it is not the turn of the player
+    pub fn guess(&mut self, game_id: Uuid, player_id: Uuid, guess: Guess) -> Result<i32, TurnError> {
+        let game = self.games.get_mut(&game_id).ok_or(TurnError::NotFound)?;
+        let result = game.guess_as(player_id, guess)?;
+        if game.is_time_attack() && game.completed().is_some() {
+            let word = self.random_word();
+            self.games.get_mut(&game_id).unwrap().next_word(&word);
+        }
+        if let Some(race) = self.race_games.get(&game_id).and_then(|race_id| self.races.get_mut(race_id)) {
+            race.update(game_id, &mut self.games);
+        }
+        Ok(result)
+    }
+
+    /// Starts a new round with a random word for the players of the game that has ended, see
+    /// [Game::rematch](base_game/struct.Game.html#method.rematch).
+    pub fn rematch(&mut self, game_id: Uuid, best_of: Option<u32>) -> Result<(), RematchError> {
+        if self.race_games.contains_key(&game_id) {
+            return Err(RematchError::Race);
+        }
+        let evil = self.games.get(&game_id).ok_or(RematchError::NotFound)?.is_evil();
+        let word = self.random_word();
+        let candidates = match evil {
+            true => self.candidates(&word),
+            false => Vec::new(),
+        };
+        self.games.get_mut(&game_id).ok_or(RematchError::NotFound)?.rematch(&word, candidates, best_of)
+    }
+
+    /// Registers a new evil game in which the word is not fixed, see [Game::evil](base_game/struct.Game.html#method.evil).
+    /// 
+    /// The candidates are all words of the words file that have the same length as a random word.
+    /// The client is limited like in [register_game_for](#method.register_game_for).
+    /// # Returns
+    /// The result of the registration or why the game is rejected, see [RegisterError]
+    pub fn register_evil_game(&mut self, client: Option<IpAddr>, max_games: usize) -> Result<RegisterResult, RegisterError> {
+        let word = self.random_word();
+        let candidates = self.candidates(&word);
+        self.insert_game_for(client, max_games, |game_id, player_id| Game::evil(game_id, player_id, &word, candidates))
+    }
+
+    /// # Returns
+    /// All words that have the same length as the word and `-` at the same positions
+    fn candidates(&self, word: &str) -> Vec<String> {
+        let shape = |word: &str| word.chars().map(|c| c == '-').collect::<Vec<bool>>();
+        let expected = shape(word);
+        self.words.list().words().iter().filter(|candidate| shape(candidate) == expected).cloned().collect()
+    }
+
+    /// Adds a bot of the strength to the race, the bot plays its own game like the other players.
+    /// 
+    /// The game of the bot counts against the client that added it, like in [join_race](#method.join_race).
+    /// # Returns
+    /// The result of the registration of the bot or why it can not join
+    pub fn add_race_bot(&mut self, race_id: Uuid, strength: u8, client: Option<IpAddr>, max_games: usize) -> Result<RegisterResult, BotError> {
+        if !BOT_STRENGTHS.contains(&strength) {
+            return Err(BotError::InvalidStrength);
+        }
+        if self.bots.values().filter(|bot| bot.race_id == Some(race_id)).count() >= MAX_BOTS {
+            return Err(BotError::TooManyBots);
+        }
+        let result = self.join_race(race_id, client, max_games).map_err(BotError::Race)?;
+        self.bots.insert(result.player_id, Bot::new(result.game_id, Some(race_id), strength));
+        Ok(result)
+    }
+
+    /// Adds a bot of the strength to the turn-based game, the bot joins like another player.
+    /// # Returns
+    /// The result of the registration of the bot or why it can not join
+    pub fn add_game_bot(&mut self, game_id: Uuid, strength: u8) -> Result<RegisterResult, BotError> {
+        if !BOT_STRENGTHS.contains(&strength) {
+            return Err(BotError::InvalidStrength);
+        }
+        if self.bots.values().filter(|bot| bot.game_id == game_id).count() >= MAX_BOTS {
+            return Err(BotError::TooManyBots);
+        }
+        let result = self.join_game(game_id).map_err(BotError::Turn)?;
+        self.bots.insert(result.player_id, Bot::new(game_id, None, strength));
+        Ok(result)
+    }
+
+    /// Lets all bots that are ready at `now` make their next move in the tests, the timer watcher computes the moves
+    /// without holding the lock.
+    #[cfg(test)]
+    fn play_bots_at(&mut self, now: Instant) -> Vec<i32> {
+        let moves = self.bot_turns(now).into_iter().filter_map(BotTurn::play).collect();
+        self.apply_bot_moves(now, moves)
+    }
+
+    /// Collects the turns of all bots that are ready at `now`.
+    /// 
+    /// A bot guesses when it can guess in its game and chooses a random word when it is the chooser of a turn-based game.
+    /// The moves are computed with [BotTurn::play](bot/struct.BotTurn.html#method.play) and applied with
+    /// [apply_bot_moves](#method.apply_bot_moves).
+    pub fn bot_turns(&self, now: Instant) -> Vec<BotTurn> {
+        let mut turns = Vec::new();
+        for (player_id, bot) in self.bots.iter().filter(|(_, bot)| bot.ready(now)) {
+            let Some(game) = self.games.get(&bot.game_id) else {
+                continue;
+            };
+            if game.next_word_chooser() == Some(*player_id) {
+                turns.push(BotTurn::choose(*player_id, self.random_word()));
+                continue;
             }
+            let turn = game.turns().map(|turns| turns.current_at(now) == *player_id).unwrap_or(true);
+            if game.status() != "playing" || !turn {
+                continue;
+            }
+            let guessed: Vec<char> = game.guessed_letters().chars().filter(|letter| letter.is_alphabetic()).collect();
+            turns.push(BotTurn::guess(*player_id, bot.strength, game.game_string(), guessed, self.words.list()));
+        }
+        turns
+    }
+
+    /// Submits the moves of the bots and schedules the next move of all bots that were ready at `now`.
+    /// 
+    /// Moves of bots that have left and guesses in games that have ended in the meantime are dropped.
+    /// # Returns
+    /// The results of the guesses, see [Game::guess](base_game/struct.Game.html#method.guess)
+    pub fn apply_bot_moves(&mut self, now: Instant, moves: Vec<(Uuid, BotMove)>) -> Vec<i32> {
+        self.bots.values_mut().for_each(|bot| bot.moved(now));
+        let mut results = Vec::new();
+        for (player_id, bot_move) in moves {
+            let Some(game_id) = self.bots.get(&player_id).map(|bot| bot.game_id) else {
+                continue;
+            };
+            match bot_move {
+                BotMove::Choose(word) => {
+                    // Fails while the bot is the only player, it tries again with its next move
+                    let _ = self.choose_word(game_id, player_id, &word);
+                },
+                BotMove::Guess(guess) => {
+                    if self.games.get(&game_id).map(|game| game.status()) != Some("playing") {
+                        continue;
+                    }
+                    if let Ok(result) = self.guess(game_id, player_id, guess) {
+                        results.push(result);
+                    }
+                },
+            }
+        }
+        results
+    }
+
+    /// # Returns
+    /// `true` when the player is a bot
+    pub fn is_bot(&self, player_id: Uuid) -> bool {
+        self.bots.contains_key(&player_id)
+    }
+
+    /// Creates a new turn-based game in which the player chooses the first word, see [Turns](turns/struct.Turns.html).
+    /// 
+    /// The client is limited like in [register_game_for](#method.register_game_for).
+    /// When `dictionary` is set the chosen words have to be contained in the words file.
+    /// # Returns
+    /// The result of the registration or why the game is rejected, see [RegisterError]
+    pub fn register_turn_game(&mut self, client: Option<IpAddr>, max_games: usize, timeout: Duration, dictionary: bool) -> Result<RegisterResult, RegisterError> {
+        self.insert_game_for(client, max_games, |game_id, player_id| Game::with_turns(game_id, player_id, timeout, dictionary))
+    }
+
+    /// Registers a new player to the turn-based game, players can only join between rounds.
+    /// # Returns
+    /// The result of the registration or why the player can not join
+    pub fn join_game(&mut self, game_id: Uuid) -> Result<RegisterResult, TurnError> {
+        let player_id = self.free_player_id();
+        if let Err(err) = self.games.get_mut(&game_id).ok_or(TurnError::NotFound).and_then(|game| game.add_player(player_id)) {
+            self.player_ids.remove(&player_id);
+            return Err(err);
+        }
+        self.players.insert(player_id, game_id);
+        Ok(RegisterResult { player_id, game_id, evicted: false })
+    }
+
+    /// Starts the next round of the turn-based game with the word that the player has chosen.
+    /// 
+    /// The word is normalized like a guess and has to be contained in the words file when the game requires it.
+    pub fn choose_word(&mut self, game_id: Uuid, player_id: Uuid, word: &str) -> Result<(), TurnError> {
+        let word = match word.parse::<Guess>().map_err(TurnError::InvalidWord)? {
+            Guess::Word(word) => word,
+            Guess::Letter(letter) => letter.to_string(),
+        };
+        let game = self.games.get_mut(&game_id).ok_or(TurnError::NotFound)?;
+        if game.turns().map(|turns| turns.dictionary()).unwrap_or(false) && !self.words.list().words().contains(&word) {
+            return Err(TurnError::NotInDictionary);
         }
-        transformed_word
+        game.choose_word(player_id, &word)
+    }
+
+    /// # Returns
+    /// The number of games that have been registered by the client and are still managed
+    pub fn games_of_client(&self, client: Option<IpAddr>) -> usize {
+        self.clients.values().filter(|address| **address == client).count()
+    }
+
+    /// Returns a random word from the words file
+    fn random_word(&self) -> String {
+        self.words.random_word()
+    }
+
+    /// Reads the words file again.
+    /// 
+    /// Games that are already running keep their word.
+    /// # Returns
+    /// The number of words that have been loaded or the error that occurred while reading the file.
+    /// When the file could not be loaded the previous words are kept.
+    pub fn reload_words(&self) -> Result<usize, WordListError> {
+        self.words.reload()
+    }
+
+    /// # Returns
+    /// The words of this game manager, can be used to reload the words without locking the game manager.
+    pub fn words(&self) -> Arc<WordSource> {
+        Arc::clone(&self.words)
+    }
+
+    /// # Returns
+    /// The number of words from which a word can be chosen
+    pub fn word_count(&self) -> usize {
+        self.words.len()
+    }
+
+    /// # Returns
+    /// The name of each loaded dictionary together with the number of words in it
+    pub fn dictionaries(&self) -> Vec<(String, usize)> {
+        let list = self.words.list();
+        vec![(list.name().to_string(), list.len())]
     }
    
     /// # Returns
@@ -92,7 +465,43 @@ impl GameManager {
     /// 
     /// `None` the player id does not appear to be assigned to a game
     pub fn game_by_player_id(&mut self, id: Uuid) -> Option<&mut Game> {
-        self.games.get_mut(&id)
+        let game_id = *self.players.get(&id)?;
+        self.game_by_id(game_id)
+    }
+
+    /// The time limits of the game are enforced before it is returned.
+    /// # Returns
+    /// 
+    /// `Some(&mut Game)` when the game with the id exists
+    /// 
+    /// `None` when no game with the id exists
+    pub fn game_by_id(&mut self, id: Uuid) -> Option<&mut Game> {
+        let game = self.games.get_mut(&id)?;
+        game.check_time();
+        Some(game)
+    }
+
+    /// Enforces the time limits of all games, see [Game::check_time](base_game/struct.Game.html#method.check_time).
+    /// 
+    /// The limits are also checked whenever a game is accessed, this ends games whose players have stopped playing.
+    pub fn check_timers(&mut self) {
+        self.check_timers_at(Instant::now());
+    }
+
+    /// Enforces the time limits of all games at the time `now`, see [check_timers](#method.check_timers).
+    fn check_timers_at(&mut self, now: Instant) {
+        self.games.values_mut().for_each(|game| game.check_time_at(now));
+    }
+
+    /// # Returns
+    /// The number of games that have been won and lost since the server started
+    pub fn results(&self) -> &GameResults {
+        &self.results
+    }
+
+    /// Returns all games that are currently managed, the oldest game comes first.
+    pub fn games(&self) -> Vec<&Game> {
+        self.game_id_history.iter().filter_map(|id| self.games.get(id)).collect()
     }
 
     /// Deletes the game for the specified user.
@@ -104,9 +513,68 @@ impl GameManager {
     /// 
     /// `false` no game found for user
     pub fn delete_game(&mut self, id: Uuid) -> bool {
-        if let Some(game) = self.games.remove(&id) {
-            self.game_ids.remove(&game.game_id());
-            self.player_ids.remove(&id);
+        match self.players.get(&id) {
+            Some(game_id) => {
+                let game_id = *game_id;
+                self.delete_game_by_id(game_id)
+            },
+            None => false,
+        }
+    }
+
+    /// Removes the player from the game, the other players keep playing. The game is deleted when only bots are left.
+    /// # Returns
+    /// `true` when the player was assigned to a game
+    pub fn leave_game(&mut self, player_id: Uuid) -> bool {
+        let Some(game_id) = self.players.get(&player_id).copied() else {
+            return false;
+        };
+        let Some(game) = self.games.get_mut(&game_id) else {
+            return false;
+        };
+        let others_left = game.player_ids().iter().any(|id| *id != player_id && !self.bots.contains_key(id));
+        if !others_left {
+            return self.delete_game_by_id(game_id);
+        }
+        game.remove_player(player_id);
+        self.players.remove(&player_id);
+        self.player_ids.remove(&player_id);
+        true
+    }
+
+    /// Deletes the game with the id and all players that are assigned to it.
+    /// # Returns
+    /// `true` game was deleted
+    /// 
+    /// `false` no game with the id exists
+    pub fn delete_game_by_id(&mut self, game_id: Uuid) -> bool {
+        if let Some(game) = self.games.remove(&game_id) {
+            for player_id in game.player_ids() {
+                self.players.remove(&player_id);
+                self.player_ids.remove(&player_id);
+            }
+            self.game_ids.remove(&game_id);
+            self.clients.remove(&game_id);
+            self.bots.retain(|_, bot| bot.game_id != game_id);
+            let mut bot_games = Vec::new();
+            if let Some(race_id) = self.race_games.remove(&game_id) {
+                if self.races.get_mut(&race_id).map(|race| race.remove_game(game_id)).unwrap_or(false) {
+                    self.races.remove(&race_id);
+                } else if let Some(race) = self.races.get(&race_id) {
+                    // A race in which only bots are left is deleted
+                    if race.game_ids().iter().all(|id| self.bots.values().any(|bot| bot.game_id == *id)) {
+                        bot_games = race.game_ids().to_vec();
+                    }
+                }
+            }
+            if let Some(position) = self.game_id_history.iter().position(|id| *id == game_id) {
+                let mut tail = self.game_id_history.split_off(position);
+                tail.pop_front();
+                self.game_id_history.append(&mut tail);
+            }
+            for bot_game in bot_games {
+                self.delete_game_by_id(bot_game);
+            }
             return true
         }
         false
@@ -137,26 +605,322 @@ impl GameManager {
     }
 }
 
+/// Converts the word to uppercase and replaces umlauts with their two letter spelling, so that the word can be guessed
+/// with the letters of the [ALPHABET](constant.ALPHABET.html).
+pub fn normalize_word(word: &str) -> String {
+    let mut transformed_word = String::new();
+    for c in word.to_uppercase().chars() {
+        match c {
+            'Ä' => transformed_word.push_str("AE"),
+            'Ö' => transformed_word.push_str("OE"),
+            'Ü' => transformed_word.push_str("UE"),
+            _ => transformed_word.push(c),
+        }
+    }
+    transformed_word
+}
+
+impl Default for GameManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Used to represent a result that occurs when [register_game](struct.GameManager.html#method.register_game) is called.
 pub struct RegisterResult {
     /// The id of the new player
     pub player_id: Uuid,
+    /// The id of the game the player was registered to
+    pub game_id: Uuid,
+    /// `true` when the oldest ended game has been deleted because the [MAX_ACTIVE_GAMES](constant.MAX_ACTIVE_GAMES.html) limit was reached
+    pub evicted: bool,
+}
+
+/// Reasons why a new game is rejected, see [register_game_for](struct.GameManager.html#method.register_game_for)
+#[derive(Debug, PartialEq, Eq)]
+pub enum RegisterError {
+    /// The client already has the maximum number of games
+    ClientLimit,
+    /// The [MAX_ACTIVE_GAMES] limit is reached and all games are still running
+    GameLimit,
+}
+
+impl Display for RegisterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegisterError::ClientLimit => write!(f, "You already have the maximum number of games, finish or delete one of them first"),
+            RegisterError::GameLimit => write!(f, "The server has reached the maximum number of games, try again later"),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{GameManager, MAX_ACTIVE_GAMES};
+    use std::{net::IpAddr, time::{Duration, Instant}};
+
+    use uuid::Uuid;
+
+    use super::{base_game::Game, bot::{BotError, BotTurn, BOT_DELAY, MAX_BOTS}, guess::{Guess, GuessError}, race::RaceError, series::RematchError, timer::Timer, turns::TurnError, GameManager, RegisterError, MAX_ACTIVE_GAMES, MAX_LIVES};
 
 
     #[test]
     fn test_max_game_limit() {
         let mut game_manager = GameManager::new();
-        let first_uuid = game_manager.register_game().player_id;
-        for _i in 1..=MAX_ACTIVE_GAMES {
-            game_manager.register_game();
+        let first = game_manager.register_game().unwrap();
+        let second = game_manager.register_game().unwrap();
+        for _i in 2..MAX_ACTIVE_GAMES {
+            game_manager.register_game().unwrap();
         }
-        let last_uuid = game_manager.register_game().player_id;
-        assert!(game_manager.game_by_player_id(first_uuid).is_none());
-        assert!(game_manager.game_by_player_id(last_uuid).is_some());
+        // Running games are not deleted to make space
+        assert_eq!(game_manager.register_game().err(), Some(RegisterError::GameLimit));
+        assert!(game_manager.game_by_player_id(first.player_id).is_some());
+        // The oldest game that has ended is deleted instead
+        game_manager.game_by_id(second.game_id).unwrap().force_end();
+        let last = game_manager.register_game().unwrap();
+        assert!(last.evicted);
+        assert!(game_manager.game_by_player_id(first.player_id).is_some());
+        assert!(game_manager.game_by_player_id(second.player_id).is_none());
+        assert!(game_manager.game_by_player_id(last.player_id).is_some());
+        assert_eq!(game_manager.games().len(), MAX_ACTIVE_GAMES);
+    }
+
+    #[test]
+    fn test_delete_game_by_id() {
+        let mut game_manager = GameManager::new();
+        let player_id = game_manager.register_game().unwrap().player_id;
+        let game_id = game_manager.game_by_player_id(player_id).unwrap().game_id();
+        assert_eq!(game_manager.games().len(), 1);
+        assert!(game_manager.delete_game_by_id(game_id));
+        assert!(game_manager.game_by_player_id(player_id).is_none());
+        assert!(game_manager.games().is_empty());
+        assert!(!game_manager.delete_game_by_id(game_id));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_games_per_client_limit() {
+        let mut game_manager = GameManager::new();
+        let client = Some(IpAddr::from([127, 0, 0, 1]));
+        let other = game_manager.register_game_for(Some(IpAddr::from([127, 0, 0, 2])), 2).unwrap().player_id;
+        let first = game_manager.register_game_for(client, 2).unwrap().player_id;
+        let (race_id, second) = game_manager.create_race(client, 2).unwrap();
+        // Further games of the client are rejected instead of replacing its running games
+        assert_eq!(game_manager.register_game_for(client, 2).err(), Some(RegisterError::ClientLimit));
+        assert_eq!(game_manager.join_race(race_id, client, 2).err(), Some(RaceError::Register(RegisterError::ClientLimit)));
+        // The game of a bot counts against the client that added it
+        assert_eq!(game_manager.add_race_bot(race_id, 5, client, 2).err(), Some(BotError::Race(RaceError::Register(RegisterError::ClientLimit))));
+        assert_eq!(game_manager.games_of_client(client), 2);
+        assert!(game_manager.game_by_player_id(first).is_some());
+        assert!(game_manager.game_by_player_id(second.player_id).is_some());
+        assert!(game_manager.game_by_player_id(other).is_some());
+        // A client can register again once one of its games is deleted
+        game_manager.delete_game_by_id(second.game_id);
+        assert!(game_manager.register_game_for(client, 2).is_ok());
+        // Clients with an unknown address share one limit
+        for _ in 0..2 {
+            assert!(game_manager.create_race(None, 2).is_ok());
+        }
+        assert_eq!(game_manager.create_race(None, 2).err(), Some(RegisterError::ClientLimit));
+    }
+
+    #[test]
+    fn test_race() {
+        let mut game_manager = GameManager::new();
+        let (race_id, first) = game_manager.create_race(None, 0).unwrap();
+        let second = game_manager.join_race(race_id, None, 0).unwrap();
+        let third = game_manager.join_race(race_id, None, 0).unwrap();
+        let word = game_manager.game_by_id(first.game_id).unwrap().solution();
+        assert_eq!(game_manager.game_by_id(second.game_id).unwrap().solution(), word);
+        assert!(game_manager.race_of_game(first.game_id).unwrap().winners.is_none());
+
+        assert_eq!(game_manager.guess(second.game_id, second.player_id, Guess::Word(word.clone())), Ok(1));
+        let race = game_manager.race_of_game(third.game_id).unwrap();
+        assert_eq!(race.winners, Some(vec![second.game_id]));
+        assert_eq!(race.word, Some(word));
+        assert_eq!(game_manager.game_by_id(first.game_id).unwrap().status(), "lost");
+        assert_eq!(game_manager.join_race(race_id, None, 0).err(), Some(RaceError::Finished));
+        // The games that the race ended are counted as lost
+        assert_eq!((game_manager.results().wins(), game_manager.results().losses()), (1, 2));
+
+        for game_id in [first.game_id, second.game_id, third.game_id] {
+            game_manager.delete_game_by_id(game_id);
+        }
+        assert_eq!(game_manager.join_race(race_id, None, 0).err(), Some(RaceError::NotFound));
+    }
+
+    #[test]
+    fn test_race_tie() {
+        let mut game_manager = GameManager::new();
+        let (race_id, first) = game_manager.create_race(None, 0).unwrap();
+        let second = game_manager.join_race(race_id, None, 0).unwrap();
+        for game_id in [first.game_id, second.game_id] {
+            game_manager.game_by_id(game_id).unwrap().force_end();
+        }
+        let race = game_manager.race_of_game(first.game_id).unwrap();
+        assert_eq!(race.winners, Some(vec![first.game_id, second.game_id]));
+        assert!(race.tie());
+    }
+
+    #[test]
+    fn test_turn_game() {
+        let mut game_manager = GameManager::new();
+        let host = game_manager.register_turn_game(None, 0, Duration::ZERO, false).unwrap();
+        let game_id = host.game_id;
+        assert_eq!(game_manager.choose_word(game_id, host.player_id, "Brücke"), Err(TurnError::NotEnoughPlayers));
+        let first = game_manager.join_game(game_id).unwrap();
+        let second = game_manager.join_game(game_id).unwrap();
+        assert_eq!(game_manager.game_by_player_id(second.player_id).unwrap().game_id(), game_id);
+        assert_eq!(game_manager.guess(game_id, first.player_id, Guess::Letter('E')), Err(TurnError::WaitingForWord));
+        assert_eq!(game_manager.choose_word(game_id, first.player_id, "Brücke"), Err(TurnError::NotChooser));
+        assert_eq!(game_manager.choose_word(game_id, host.player_id, "Br1cke"), Err(TurnError::InvalidWord(GuessError::InvalidCharacter('1'))));
+        assert_eq!(game_manager.choose_word(game_id, host.player_id, "Brücke"), Ok(()));
+        assert_eq!(game_manager.join_game(game_id).err(), Some(TurnError::RoundRunning));
+
+        assert_eq!(game_manager.guess(game_id, host.player_id, Guess::Letter('E')), Err(TurnError::NotYourTurn));
+        assert_eq!(game_manager.guess(game_id, second.player_id, Guess::Letter('E')), Err(TurnError::NotYourTurn));
+        assert_eq!(game_manager.guess(game_id, first.player_id, Guess::Letter('E')), Ok(2));
+        assert_eq!(game_manager.guess(game_id, second.player_id, Guess::Word(String::from("BRUECKE"))), Ok(1));
+
+        // The next player chooses the word of the second round
+        assert_eq!(game_manager.choose_word(game_id, host.player_id, "Auto"), Err(TurnError::NotChooser));
+        assert_eq!(game_manager.choose_word(game_id, first.player_id, "Auto"), Ok(()));
+        assert_eq!(game_manager.guess(game_id, second.player_id, Guess::Letter('A')), Ok(2));
+        assert_eq!(game_manager.guess(game_id, host.player_id, Guess::Letter('U')), Ok(2));
+        assert_eq!(game_manager.game_by_id(game_id).unwrap().game_string(), "A U _ _");
+        assert_eq!(game_manager.game_by_id(game_id).unwrap().series().wins(second.player_id), 1);
+        assert_eq!(game_manager.rematch(game_id, None), Err(RematchError::TurnBased));
+
+        // The round ends when the chooser leaves and the next player chooses the next word
+        assert!(game_manager.leave_game(first.player_id));
+        assert!(game_manager.game_by_id(game_id).unwrap().completed().is_some());
+        assert_eq!(game_manager.choose_word(game_id, host.player_id, "Haus"), Err(TurnError::NotChooser));
+        assert_eq!(game_manager.choose_word(game_id, second.player_id, "Haus"), Ok(()));
+    }
+
+    #[test]
+    fn test_bots() {
+        let mut game_manager = GameManager::new();
+        let (race_id, host) = game_manager.create_race(None, 0).unwrap();
+        assert_eq!(game_manager.add_race_bot(race_id, 11, None, 0).err(), Some(BotError::InvalidStrength));
+        let bot = game_manager.add_race_bot(race_id, 10, None, 0).unwrap();
+        assert!(game_manager.is_bot(bot.player_id));
+        assert!(!game_manager.is_bot(host.player_id));
+        // The bot waits before each move
+        let start = Instant::now();
+        game_manager.play_bots_at(start);
+        assert_eq!(game_manager.game_by_id(bot.game_id).unwrap().guessed_letters().trim(), "");
+        assert_eq!(game_manager.play_bots_at(start + BOT_DELAY).len(), 1);
+        for round in 2..=40 {
+            game_manager.play_bots_at(start + BOT_DELAY * round);
+        }
+        assert_ne!(game_manager.game_by_id(bot.game_id).unwrap().status(), "playing");
+        // The race is deleted once only bots are left
+        game_manager.delete_game_by_id(host.game_id);
+        assert!(game_manager.game_by_id(bot.game_id).is_none());
+        assert!(!game_manager.is_bot(bot.player_id));
+
+        // The moves are computed without the lock, a guess in a game that has ended in the meantime is dropped
+        let (race_id, _) = game_manager.create_race(None, 0).unwrap();
+        let bot = game_manager.add_race_bot(race_id, 10, None, 0).unwrap();
+        let now = Instant::now() + BOT_DELAY;
+        let moves: Vec<_> = game_manager.bot_turns(now).into_iter().filter_map(BotTurn::play).collect();
+        assert_eq!(moves.len(), 1);
+        game_manager.game_by_id(bot.game_id).unwrap().force_end();
+        assert!(game_manager.apply_bot_moves(now, moves).is_empty());
+        assert_eq!(game_manager.game_by_id(bot.game_id).unwrap().guessed_letters().trim(), "");
+        assert!(game_manager.bot_turns(now).is_empty());
+
+        let host = game_manager.register_turn_game(None, 0, Duration::ZERO, false).unwrap();
+        for _ in 0..MAX_BOTS {
+            game_manager.add_game_bot(host.game_id, 5).unwrap();
+        }
+        assert_eq!(game_manager.add_game_bot(host.game_id, 5).err(), Some(BotError::TooManyBots));
+        assert_eq!(game_manager.choose_word(host.game_id, host.player_id, "Donaudampfschifffahrt"), Ok(()));
+        game_manager.play_bots_at(Instant::now() + BOT_DELAY);
+        let game = game_manager.game_by_id(host.game_id).unwrap();
+        assert!(!game.guessed_letters().trim().is_empty());
+        assert_ne!(game.turns().unwrap().current(), host.player_id);
+    }
+
+    #[test]
+    fn test_rematch() {
+        let mut game_manager = GameManager::new();
+        let result = game_manager.register_game().unwrap();
+        let game_id = result.game_id;
+        assert_eq!(game_manager.rematch(game_id, Some(3)), Err(RematchError::RoundRunning));
+        game_manager.game_by_id(game_id).unwrap().force_end();
+        assert_eq!(game_manager.rematch(game_id, Some(0)), Err(RematchError::InvalidLength));
+        assert_eq!(game_manager.rematch(game_id, Some(3)), Ok(()));
+        for _ in 0..2 {
+            let word = game_manager.game_by_id(game_id).unwrap().solution();
+            assert_eq!(game_manager.guess(game_id, result.player_id, Guess::Word(word)), Ok(1));
+            assert_eq!(game_manager.rematch(game_id, None), Ok(()));
+        }
+        // The series was won 2 to 0, the next rematch starts a new series
+        let game = game_manager.game_by_player_id(result.player_id).unwrap();
+        assert_eq!(game.game_id(), game_id);
+        assert_eq!(game.status(), "playing");
+        assert_eq!((game.series().best_of(), game.series().rounds()), (Some(3), 0));
+        assert_eq!(game_manager.rematch(super::Uuid::new_v4(), None), Err(RematchError::NotFound));
+        let (_, racer) = game_manager.create_race(None, 0).unwrap();
+        assert_eq!(game_manager.rematch(racer.game_id, None), Err(RematchError::Race));
+    }
+
+    #[test]
+    fn test_evil_game() {
+        let candidates = ["HUND", "HAND", "WAND", "MAUS", "BAUM"].map(String::from).to_vec();
+        let mut game = Game::evil(Uuid::new_v4(), Uuid::new_v4(), "HUND", candidates);
+        // The family without E is the largest, afterwards HAND, WAND, MAUS and BAUM all contain an A at position 1
+        assert_eq!(game.guess(Guess::Letter('E')), 3);
+        assert_eq!(game.guess(Guess::Letter('A')), 2);
+        assert_eq!(game.game_string(), "_ A _ _");
+        // HAND and WAND end with ND, MAUS and BAUM do not, the family with fewer revealed letters wins the tie
+        assert_eq!(game.guess(Guess::Letter('N')), 3);
+        assert_eq!(game.guess(Guess::Word(String::from("MAUS"))), 3);
+        assert_eq!(game.guess(Guess::Word(String::from("BAUM"))), 1);
+        assert_eq!(game.word(), Some(String::from("BAUM")));
+        assert_eq!(game.lives(), MAX_LIVES - 3);
+    }
+
+    #[test]
+    fn test_time_limits() {
+        let mut game_manager = GameManager::new();
+        let guesses = game_manager.register_timed_game(None, 0, Timer::new(Some(Duration::from_millis(100)), None)).unwrap();
+        let game = game_manager.register_timed_game(None, 0, Timer::new(None, Some(Duration::from_millis(100)))).unwrap();
+        game_manager.check_timers_at(Instant::now() + Duration::from_millis(250));
+        assert_eq!(game_manager.game_by_id(guesses.game_id).unwrap().lives(), MAX_LIVES - 2);
+        let game = game_manager.game_by_player_id(game.player_id).unwrap();
+        assert_eq!(game.status(), "lost");
+        assert_eq!(game.series().rounds(), 1);
+        // Games that end because their time has run out are counted as lost
+        assert_eq!((game_manager.results().wins(), game_manager.results().losses()), (0, 1));
+    }
+
+    #[test]
+    fn test_time_attack() {
+        let mut game_manager = GameManager::new();
+        let result = game_manager.register_time_attack(None, 0, Duration::from_millis(100)).unwrap();
+        let game_id = result.game_id;
+        let word = game_manager.game_by_id(game_id).unwrap().solution();
+        assert_eq!(game_manager.guess(game_id, result.player_id, Guess::Word(word)), Ok(1));
+        assert_eq!(game_manager.guess(game_id, result.player_id, Guess::Word(String::from("XYZ"))), Ok(3));
+        let game = game_manager.game_by_id(game_id).unwrap();
+        assert_eq!(game.status(), "playing");
+        assert_eq!(game.series().solved(), 1);
+        game_manager.check_timers_at(Instant::now() + Duration::from_millis(150));
+        assert_eq!(game_manager.game_by_id(game_id).unwrap().status(), "won");
+        assert_eq!(game_manager.guess(game_id, result.player_id, Guess::Letter('E')), Ok(6));
+    }
+
+    #[test]
+    fn test_turn_game_dictionary() {
+        let mut game_manager = GameManager::new();
+        let host = game_manager.register_turn_game(None, 0, Duration::ZERO, true).unwrap();
+        game_manager.join_game(host.game_id).unwrap();
+        assert_eq!(game_manager.choose_word(host.game_id, host.player_id, "Xyzzy"), Err(TurnError::NotInDictionary));
+        let word = game_manager.words().random_word();
+        assert_eq!(game_manager.choose_word(host.game_id, host.player_id, &word), Ok(()));
+        assert_eq!(game_manager.join_game(super::Uuid::new_v4()).err(), Some(TurnError::NotFound));
+        let single = game_manager.register_game().unwrap();
+        assert_eq!(game_manager.join_game(single.game_id).err(), Some(TurnError::NotTurnBased));
+    }
+}
@@ -0,0 +1,162 @@
+use std::{collections::HashMap, fmt::Display};
+
+use uuid::Uuid;
+
+use super::{base_game::Game, RegisterError};
+
+/// A race in which several players try to guess the same word in their own [Game](../base_game/struct.Game.html).
+///
+/// The player that solves the word first wins, the games of the other players are ended at that moment. When all
+/// players lose, the players that revealed the most letters win, which can result in a tie.
+pub struct Race {
+    /// The id with which other players can join the race
+    id: Uuid,
+    /// The word that all players have to guess
+    word: String,
+    /// The games of the players in the order in which the players joined
+    games: Vec<Uuid>,
+    /// The game that solved the word first
+    winner: Option<Uuid>,
+}
+
+/// Reasons why a player can not join a race
+#[derive(Debug, PartialEq, Eq)]
+pub enum RaceError {
+    /// No race with the id exists
+    NotFound,
+    /// The race has already been decided
+    Finished,
+    /// The game of the new player is rejected
+    Register(RegisterError),
+}
+
+impl Display for RaceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RaceError::NotFound => write!(f, "The race does not exist"),
+            RaceError::Finished => write!(f, "The race has already finished"),
+            RaceError::Register(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+/// Progress of a single player in a race, does not contain the guessed letters
+pub struct Racer {
+    pub game_id: Uuid,
+    /// Number of letters that have been revealed
+    pub revealed: usize,
+    /// Number of letters of the word
+    pub letters: usize,
+    pub lives: i32,
+    /// `playing`, `won` or `lost`
+    pub status: &'static str,
+}
+
+/// The state of a race as it is shown to the players
+pub struct RaceState {
+    pub id: Uuid,
+    /// The word, only set when the race has finished
+    pub word: Option<String>,
+    /// The players in the order in which they joined
+    pub racers: Vec<Racer>,
+    /// The games of the players that won, only set when the race has finished
+    pub winners: Option<Vec<Uuid>>,
+}
+
+impl RaceState {
+    /// # Returns
+    /// `true` when more than one player won the race
+    pub fn tie(&self) -> bool {
+        self.winners.as_ref().map(|winners| winners.len() > 1).unwrap_or(false)
+    }
+}
+
+impl Race {
+    /// Creates a new race in which the first player plays in the game with the id
+    pub fn new(id: Uuid, word: String, game_id: Uuid) -> Self {
+        Self {
+            id,
+            word,
+            games: vec![game_id],
+            winner: None,
+        }
+    }
+
+    /// # Returns
+    /// The id of this race
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// # Returns
+    /// The word that all players have to guess
+    pub fn word(&self) -> &str {
+        &self.word
+    }
+
+    /// # Returns
+    /// The ids of the games of all players in the order in which they joined
+    pub fn game_ids(&self) -> &[Uuid] {
+        &self.games
+    }
+
+    /// Adds the game of a player that joined the race
+    pub fn add_game(&mut self, game_id: Uuid) {
+        self.games.push(game_id);
+    }
+
+    /// Removes the game of a player, for example because the game was deleted.
+    /// # Returns
+    /// `true` when no player is left in the race
+    pub fn remove_game(&mut self, game_id: Uuid) -> bool {
+        self.games.retain(|id| *id != game_id);
+        self.games.is_empty()
+    }
+
+    /// Checks if a guess in the game decided the race.
+    ///
+    /// When the game has been won it is the winner of the race and the games of all other players are ended.
+    pub fn update(&mut self, game_id: Uuid, games: &mut HashMap<Uuid, Game>) {
+        if self.winner.is_some() || games.get(&game_id).and_then(Game::completed) != Some(true) {
+            return;
+        }
+        self.winner = Some(game_id);
+        for id in &self.games {
+            if let Some(game) = games.get_mut(id) {
+                game.force_end();
+            }
+        }
+    }
+
+    /// # Returns
+    /// `true` when a player has solved the word or all players have completed their game
+    pub fn finished(&self, games: &HashMap<Uuid, Game>) -> bool {
+        self.winner.is_some() || self.games.iter().filter_map(|id| games.get(id)).all(|game| game.completed().is_some())
+    }
+
+    /// # Returns
+    /// The progress of all players and the result when the race has finished
+    pub fn state(&self, games: &HashMap<Uuid, Game>) -> RaceState {
+        let racers: Vec<Racer> = self.games.iter().filter_map(|id| games.get(id)).map(|game| {
+            let (revealed, letters) = game.progress();
+            Racer {
+                game_id: game.game_id(),
+                revealed,
+                letters,
+                lives: game.lives(),
+                status: game.status(),
+            }
+        }).collect();
+        if !self.finished(games) {
+            return RaceState { id: self.id, word: None, racers, winners: None };
+        }
+        let winners = match self.winner {
+            Some(winner) => vec![winner],
+            None => {
+                let most_revealed = racers.iter().map(|racer| racer.revealed).max().unwrap_or(0);
+                racers.iter().filter(|racer| racer.revealed == most_revealed).map(|racer| racer.game_id).collect()
+            },
+        };
+        RaceState { id: self.id, word: Some(self.word.clone()), racers, winners: Some(winners) }
+    }
+}
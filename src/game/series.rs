@@ -0,0 +1,147 @@
+use std::{collections::HashMap, fmt::Display};
+
+use uuid::Uuid;
+
+/// The maximum number of rounds of a best-of series
+pub const MAX_SERIES_LENGTH: u32 = 25;
+
+/// Reasons why a rematch can not be started
+#[derive(Debug, PartialEq, Eq)]
+pub enum RematchError {
+    /// No game with the id exists
+    NotFound,
+    /// The current round has not ended yet
+    RoundRunning,
+    /// Turn-based games continue when the next word is chosen
+    TurnBased,
+    /// The game is part of a race, the players have to start a new race
+    Race,
+    /// The length of the series is `0` or longer than [MAX_SERIES_LENGTH]
+    InvalidLength,
+}
+
+impl Display for RematchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RematchError::NotFound => write!(f, "The game does not exist"),
+            RematchError::RoundRunning => write!(f, "The game has not ended yet"),
+            RematchError::TurnBased => write!(f, "Turn-based games continue when the next word is chosen"),
+            RematchError::Race => write!(f, "Games of a race can not be rematched, start a new race instead"),
+            RematchError::InvalidLength => write!(f, "A series has to be between 1 and {} rounds long", MAX_SERIES_LENGTH),
+        }
+    }
+}
+
+/// Tally of the rounds that the players of a game have played in a row.
+///
+/// In a normal game the players guess together as a team, a round is won by the team when the word is solved and lost
+/// otherwise. In a turn-based game the player that solves the word wins the round, when the word is not solved the
+/// chooser wins it.
+///
+/// A best-of-N series is decided once one side has won the majority of the rounds or all rounds have been played.
+/// Without a length the rounds are counted until the game is deleted.
+pub struct Series {
+    /// Number of rounds of the series, `None` when the rounds are only counted
+    best_of: Option<u32>,
+    /// `true` when the players play together as a team
+    team: bool,
+    /// Number of rounds that have ended
+    rounds: u32,
+    /// Number of rounds in which the word was solved
+    solved: u32,
+    /// Number of rounds that each player has won, only used when the players do not play as a team
+    wins: HashMap<Uuid, u32>,
+}
+
+impl Series {
+    pub fn new(best_of: Option<u32>, team: bool) -> Self {
+        Self {
+            best_of,
+            team,
+            rounds: 0,
+            solved: 0,
+            wins: HashMap::new(),
+        }
+    }
+
+    /// # Returns
+    /// The number of rounds of the series, `None` when the rounds are only counted
+    pub fn best_of(&self) -> Option<u32> {
+        self.best_of
+    }
+
+    /// # Returns
+    /// The number of rounds that have ended
+    pub fn rounds(&self) -> u32 {
+        self.rounds
+    }
+
+    /// # Returns
+    /// The number of rounds in which the word was solved
+    pub fn solved(&self) -> u32 {
+        self.solved
+    }
+
+    /// # Returns
+    /// The number of rounds the player has won, always `0` when the players play as a team
+    pub fn wins(&self, player_id: Uuid) -> u32 {
+        self.wins.get(&player_id).copied().unwrap_or(0)
+    }
+
+    /// Records the result of a round that has ended.
+    ///
+    /// `winner` is the player that won the round when the players do not play as a team.
+    pub fn record(&mut self, solved: bool, winner: Option<Uuid>) {
+        self.rounds += 1;
+        self.solved += u32::from(solved);
+        if let (false, Some(winner)) = (self.team, winner) {
+            *self.wins.entry(winner).or_insert(0) += 1;
+        }
+    }
+
+    /// # Returns
+    /// `true` when the series has a length and is decided
+    pub fn finished(&self) -> bool {
+        let Some(best_of) = self.best_of else {
+            return false;
+        };
+        let leading = match self.team {
+            true => self.solved.max(self.rounds - self.solved),
+            false => self.wins.values().copied().max().unwrap_or(0),
+        };
+        self.rounds >= best_of || leading > best_of / 2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::Series;
+
+    #[test]
+    fn test_team_series() {
+        let mut series = Series::new(Some(3), true);
+        series.record(true, None);
+        series.record(false, None);
+        assert!(!series.finished());
+        series.record(true, None);
+        assert!(series.finished());
+        assert_eq!((series.rounds(), series.solved()), (3, 2));
+        assert!(!Series::new(None, true).finished());
+    }
+
+    #[test]
+    fn test_player_series() {
+        let players = [Uuid::new_v4(), Uuid::new_v4()];
+        let mut series = Series::new(Some(5), false);
+        series.record(true, Some(players[0]));
+        series.record(false, Some(players[1]));
+        series.record(true, Some(players[0]));
+        assert!(!series.finished());
+        series.record(true, Some(players[0]));
+        assert!(series.finished());
+        assert_eq!(series.wins(players[0]), 3);
+        assert_eq!(series.wins(players[1]), 1);
+    }
+}
@@ -0,0 +1,171 @@
+use std::{collections::HashMap, ops::RangeInclusive};
+
+use rand::{seq::SliceRandom, Rng};
+use uuid::Uuid;
+
+use super::{base_game::Game, guess::Guess, ALPHABET, MAX_LIVES};
+
+/// Strengths that a bot can have, `10` always chooses the best guess
+pub const BOT_STRENGTHS: RangeInclusive<u8> = 1..=10;
+
+/// Score of a letter that has not been guessed yet
+#[derive(Clone, Debug, PartialEq)]
+pub struct LetterScore {
+    pub letter: char,
+    /// Expected information in bits that guessing the letter reveals about the word
+    pub information: f64,
+    /// Share of the candidates that contain the letter
+    pub probability: f64,
+}
+
+/// Guesses letters like a player that knows all words of the dictionary.
+///
+/// The solver keeps the words that match the game string and the guessed letters. A letter splits these candidates into
+/// families by the positions at which it occurs, the letters are ranked by the entropy of this split, so the best letter
+/// is the one that narrows the candidates down the most on average.
+pub struct Solver<'a> {
+    /// The words that are still possible
+    candidates: Vec<&'a str>,
+    /// All letters that have been guessed, correct and wrong ones
+    guessed: Vec<char>,
+}
+
+impl<'a> Solver<'a> {
+    /// Creates a solver for the state of a game.
+    ///
+    /// `game_string` is formatted like [Game::game_string](../base_game/struct.Game.html#method.game_string), `guessed`
+    /// contains all letters that have been guessed.
+    pub fn new(words: &'a [String], game_string: &str, guessed: &[char]) -> Self {
+        let pattern: Vec<Option<char>> = game_string.split(' ').map(|shown| match shown {
+            "_" => None,
+            shown => shown.chars().next(),
+        }).collect();
+        let candidates = words.iter().map(String::as_str).filter(|word| {
+            word.chars().count() == pattern.len() && word.chars().zip(&pattern).all(|(letter, shown)| match shown {
+                Some(shown) => letter == *shown,
+                None => !guessed.contains(&letter),
+            })
+        }).collect();
+        Self { candidates, guessed: guessed.to_vec() }
+    }
+
+    /// # Returns
+    /// The words that match the game string and the guessed letters
+    pub fn candidates(&self) -> &[&'a str] {
+        &self.candidates
+    }
+
+    /// # Returns
+    /// All letters that have not been guessed yet, the best letter comes first.
+    ///
+    /// Letters that reveal the same information are ordered by the chance that they are in the word.
+    pub fn rank(&self) -> Vec<LetterScore> {
+        let total = self.candidates.len() as f64;
+        let mut scores: Vec<LetterScore> = ALPHABET.chars().filter(|letter| !self.guessed.contains(letter)).map(|letter| {
+            let mut families: HashMap<Vec<usize>, usize> = HashMap::new();
+            for candidate in &self.candidates {
+                let positions = candidate.chars().enumerate().filter(|(_, c)| *c == letter).map(|(index, _)| index).collect();
+                *families.entry(positions).or_insert(0) += 1;
+            }
+            let information = families.values().map(|count| {
+                let share = *count as f64 / total;
+                -share * share.log2()
+            }).sum::<f64>();
+            let misses = families.get(&Vec::new()).copied().unwrap_or(0) as f64;
+            LetterScore {
+                letter,
+                information: information.max(0.0),
+                probability: if total > 0.0 { 1.0 - misses / total } else { 0.0 },
+            }
+        }).collect();
+        scores.sort_by(|a, b| b.information.total_cmp(&a.information).then(b.probability.total_cmp(&a.probability)).then(a.letter.cmp(&b.letter)));
+        scores
+    }
+
+    /// # Returns
+    /// The word when only one candidate is left, otherwise the best letter.
+    /// `None` when all letters have been guessed.
+    pub fn suggest(&self) -> Option<Guess> {
+        if let [word] = self.candidates[..] {
+            return Some(Guess::Word(word.to_string()));
+        }
+        self.rank().first().map(|score| Guess::Letter(score.letter))
+    }
+
+    /// Guesses like a player of the strength, see [BOT_STRENGTHS](constant.BOT_STRENGTHS.html).
+    ///
+    /// The best guess is chosen with a chance of `strength / 10`, otherwise a random letter that has not been guessed
+    /// yet. A random letter is also chosen when no word of the dictionary matches.
+    pub fn guess_with_strength(&self, strength: u8, rng: &mut impl Rng) -> Option<Guess> {
+        if self.candidates.is_empty() || rng.gen_range(0..10) >= strength {
+            let letters: Vec<char> = ALPHABET.chars().filter(|letter| !self.guessed.contains(letter)).collect();
+            return letters.choose(rng).map(|letter| Guess::Letter(*letter));
+        }
+        self.suggest()
+    }
+}
+
+/// Result of a game that the solver played on its own
+#[derive(Debug, PartialEq, Eq)]
+pub struct SolveResult {
+    /// Number of guesses that were submitted
+    pub guesses: u32,
+    /// Number of guesses that cost a life
+    pub wrong: u32,
+    pub won: bool,
+}
+
+/// Plays a game with the word and always submits the best guess, the words are the dictionary of the solver.
+///
+/// This measures how hard the word is for a player that knows all words and guesses perfectly.
+pub fn solve(words: &[String], word: &str) -> SolveResult {
+    let mut game = Game::with_word(Uuid::nil(), Uuid::nil(), word, MAX_LIVES);
+    let mut result = SolveResult { guesses: 0, wrong: 0, won: false };
+    while game.completed().is_none() {
+        let guessed: Vec<char> = game.guessed_letters().chars().filter(|letter| letter.is_alphabetic()).collect();
+        let Some(guess) = Solver::new(words, &game.game_string(), &guessed).suggest() else {
+            break;
+        };
+        result.guesses += 1;
+        if matches!(game.guess(guess), 3 | 4) {
+            result.wrong += 1;
+        }
+    }
+    result.won = game.completed() == Some(true);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::guess::Guess;
+
+    use super::{solve, SolveResult, Solver};
+
+    #[test]
+    fn test_solver() {
+        let words: Vec<String> = ["HUND", "HAND", "WAND", "MAUS", "AUTO-BAHN"].map(String::from).to_vec();
+        let solver = Solver::new(&words, "_ _ _ _", &[]);
+        assert_eq!(solver.candidates(), ["HUND", "HAND", "WAND", "MAUS"]);
+        // A splits the words into HAND, WAND, MAUS and HUND, N into HUND, HAND, WAND and MAUS
+        let rank = solver.rank();
+        assert!(rank[0].information > 0.8);
+        assert_eq!(rank.last().unwrap().information, 0.0);
+
+        let solver = Solver::new(&words, "_ A _ _", &['A', 'E']);
+        assert_eq!(solver.candidates(), ["HAND", "WAND", "MAUS"]);
+        let solver = Solver::new(&words, "_ A N D", &['A', 'N', 'D', 'W']);
+        assert_eq!(solver.suggest(), Some(Guess::Word(String::from("HAND"))));
+        assert_eq!(Solver::new(&words, "A _ _ _ - _ A _ _", &['A']).candidates(), ["AUTO-BAHN"]);
+        assert!(Solver::new(&words, "A _ _ _ - _ _ _ _", &['A']).candidates().is_empty());
+    }
+
+    #[test]
+    fn test_solve() {
+        let words: Vec<String> = ["HUND", "HAND", "WAND", "MAUS"].map(String::from).to_vec();
+        let result = solve(&words, "MAUS");
+        assert!(result.won);
+        assert!(result.wrong <= 1);
+        // Without matching words the letters are guessed in alphabetical order until the lives are gone
+        assert_eq!(solve(&words, "XYZ"), SolveResult { guesses: 7, wrong: 7, won: false });
+    }
+}
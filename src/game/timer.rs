@@ -0,0 +1,96 @@
+use std::time::{Duration, Instant};
+
+/// Duration of a time-attack game when no duration is requested
+pub const DEFAULT_TIME_ATTACK_DURATION: Duration = Duration::from_secs(120);
+
+/// The longest time limit that can be set for a guess, a game or a turn
+pub const MAX_TIME_LIMIT: Duration = Duration::from_secs(60 * 60);
+
+/// Time limits of a game that are enforced by the server.
+///
+/// The limits are checked lazily when the game is accessed, so the timer only measures the time and the
+/// [Game](../base_game/struct.Game.html) decides what happens when a limit has run out.
+pub struct Timer {
+    /// Time the players have for each guess, every time it runs out a life is lost
+    guess_limit: Option<Duration>,
+    /// Time after which the game ends
+    game_limit: Option<Duration>,
+    /// The time at which the game or the current round started
+    started: Instant,
+    /// The time from which the time for the next guess is measured
+    last_guess: Instant,
+}
+
+impl Timer {
+    pub fn new(guess_limit: Option<Duration>, game_limit: Option<Duration>) -> Self {
+        let now = Instant::now();
+        Self {
+            guess_limit,
+            game_limit,
+            started: now,
+            last_guess: now,
+        }
+    }
+
+    /// Starts measuring both limits again, used when a new round starts
+    pub fn restart(&mut self) {
+        self.started = Instant::now();
+        self.last_guess = self.started;
+    }
+
+    /// Starts measuring the time for the next guess again
+    pub fn guessed(&mut self) {
+        self.last_guess = Instant::now();
+    }
+
+    /// # Returns
+    /// How often the time for a guess has run out until `now` since the last call or the last guess
+    pub fn expired_guesses(&mut self, now: Instant) -> u32 {
+        let Some(limit) = self.guess_limit.filter(|limit| !limit.is_zero()) else {
+            return 0;
+        };
+        let expired = (now.saturating_duration_since(self.last_guess).as_nanos() / limit.as_nanos()) as u32;
+        self.last_guess += limit * expired;
+        expired
+    }
+
+    /// # Returns
+    /// `true` when the time of the game has run out at `now`
+    pub fn game_expired(&self, now: Instant) -> bool {
+        self.game_time_left(now).map(|left| left.is_zero()).unwrap_or(false)
+    }
+
+    /// # Returns
+    /// The time that is left for the next guess at `now` or `None` when guesses are not limited
+    pub fn guess_time_left(&self, now: Instant) -> Option<Duration> {
+        Some(self.guess_limit?.saturating_sub(now.saturating_duration_since(self.last_guess)))
+    }
+
+    /// # Returns
+    /// The time that is left for the game at `now` or `None` when the game is not limited
+    pub fn game_time_left(&self, now: Instant) -> Option<Duration> {
+        Some(self.game_limit?.saturating_sub(now.saturating_duration_since(self.started)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::Timer;
+
+    #[test]
+    fn test_timer() {
+        let mut timer = Timer::new(Some(Duration::from_millis(100)), Some(Duration::from_millis(400)));
+        let start = timer.started;
+        assert_eq!(timer.expired_guesses(start), 0);
+        assert_eq!(timer.expired_guesses(start + Duration::from_millis(250)), 2);
+        assert_eq!(timer.expired_guesses(start + Duration::from_millis(250)), 0);
+        assert_eq!(timer.guess_time_left(start + Duration::from_millis(250)), Some(Duration::from_millis(50)));
+        assert!(!timer.game_expired(start + Duration::from_millis(250)));
+        assert!(timer.game_expired(start + Duration::from_millis(400)));
+        timer.restart();
+        assert!(!timer.game_expired(Instant::now()));
+        assert!(Timer::new(None, None).game_time_left(start).is_none());
+    }
+}
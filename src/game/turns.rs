@@ -0,0 +1,286 @@
+use std::{fmt::Display, time::{Duration, Instant}};
+
+use uuid::Uuid;
+
+use super::guess::GuessError;
+
+/// Time a player has for a guess before the turn is passed to the next player
+pub const DEFAULT_TURN_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Reasons why an action is not allowed in a turn-based game
+#[derive(Debug, PartialEq, Eq)]
+pub enum TurnError {
+    /// No game with the id exists
+    NotFound,
+    /// The game is not turn-based
+    NotTurnBased,
+    /// Another player has to guess now
+    NotYourTurn,
+    /// Another player has to choose the word of the round
+    NotChooser,
+    /// The word of the round has not been chosen yet
+    WaitingForWord,
+    /// The round is still running, the word can not be changed and no players can join
+    RoundRunning,
+    /// At least one player has to join before the first word can be chosen
+    NotEnoughPlayers,
+    /// The chosen word can not be guessed
+    InvalidWord(GuessError),
+    /// The chosen word is not contained in the words file, only checked when the game requires it
+    NotInDictionary,
+}
+
+impl Display for TurnError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TurnError::NotFound => write!(f, "The game does not exist"),
+            TurnError::NotTurnBased => write!(f, "The game is not turn-based"),
+            TurnError::NotYourTurn => write!(f, "It is not your turn"),
+            TurnError::NotChooser => write!(f, "Another player chooses the word of this round"),
+            TurnError::WaitingForWord => write!(f, "The word of this round has not been chosen yet"),
+            TurnError::RoundRunning => write!(f, "The round is still running"),
+            TurnError::NotEnoughPlayers => write!(f, "At least two players are required"),
+            TurnError::InvalidWord(err) => write!(f, "The word is invalid: {}", err),
+            TurnError::NotInDictionary => write!(f, "The word is not contained in the dictionary"),
+        }
+    }
+}
+
+/// Order of the players in a turn-based game.
+///
+/// In each round one player chooses the word and the other players guess one after another in the order in which they
+/// joined. A player that does not guess within the timeout is skipped. The role of the chooser is passed on to the
+/// next player in each round.
+pub struct Turns {
+    /// All players in the order in which they joined, the first player is the host
+    order: Vec<Uuid>,
+    /// Index of the player that chooses the word of the current round
+    chooser: usize,
+    /// Index of the player that guessed last or the chooser when nobody guessed in this round
+    previous: usize,
+    /// The time at which the turn of the current player started
+    started: Instant,
+    /// Time a player has for a guess, a zero duration disables the timeout
+    timeout: Duration,
+    /// Number of the current round, `0` until the first word has been chosen
+    round: u32,
+    /// `true` when the chosen words have to be contained in the words file
+    dictionary: bool,
+}
+
+impl Turns {
+    /// Creates the order of a new game in which the host chooses the first word
+    pub fn new(host: Uuid, timeout: Duration, dictionary: bool) -> Self {
+        Self {
+            order: vec![host],
+            chooser: 0,
+            previous: 0,
+            started: Instant::now(),
+            timeout,
+            round: 0,
+            dictionary,
+        }
+    }
+
+    /// Adds a player to the end of the order
+    pub fn add(&mut self, player_id: Uuid) {
+        self.order.push(player_id);
+    }
+
+    /// # Returns
+    /// All players in the order in which they joined
+    pub fn players(&self) -> &[Uuid] {
+        &self.order
+    }
+
+    /// # Returns
+    /// The player that chooses the word of the current round
+    pub fn chooser(&self) -> Uuid {
+        self.order[self.chooser]
+    }
+
+    /// # Returns
+    /// The player that chooses the word of the next round
+    pub fn next_chooser(&self) -> Uuid {
+        self.order[(self.chooser + 1) % self.order.len()]
+    }
+
+    /// # Returns
+    /// The number of the current round, `0` until the first word has been chosen
+    pub fn round(&self) -> u32 {
+        self.round
+    }
+
+    /// # Returns
+    /// The time a player has for a guess
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// # Returns
+    /// `true` when the chosen words have to be contained in the words file
+    pub fn dictionary(&self) -> bool {
+        self.dictionary
+    }
+
+    /// Starts the next round, the roles are rotated except for the first round in which the host chooses.
+    pub fn start_round(&mut self) {
+        if self.round > 0 {
+            self.chooser = (self.chooser + 1) % self.order.len();
+        }
+        self.round += 1;
+        self.previous = self.chooser;
+        self.started = Instant::now();
+    }
+
+    /// # Returns
+    /// The player that has to guess now, players whose time has run out are skipped
+    pub fn current(&self) -> Uuid {
+        self.current_at(Instant::now())
+    }
+
+    /// # Returns
+    /// The player that has to guess at `now`, see [current](#method.current)
+    pub fn current_at(&self, now: Instant) -> Uuid {
+        self.order[self.current_index(now)]
+    }
+
+    /// # Returns
+    /// The time that is left for the current player at `now` or `None` when the timeout is disabled
+    pub fn time_left(&self, now: Instant) -> Option<Duration> {
+        if self.timeout.is_zero() {
+            return None;
+        }
+        let elapsed = now.saturating_duration_since(self.started).as_nanos() % self.timeout.as_nanos();
+        Some(self.timeout - Duration::from_nanos(elapsed as u64))
+    }
+
+    /// Removes the player from the order.
+    ///
+    /// The player that has to guess stays the same, when it was the player that left the turn is passed on to the next
+    /// player. When the chooser leaves the next word is chosen by the player after the one that left: before the first
+    /// round that player becomes the chooser, afterwards the previous player keeps the role of the ended round so that the
+    /// rotation passes it on. The game has to end a running round first, see
+    /// [remove_player](../base_game/struct.Game.html#method.remove_player).
+    pub fn remove(&mut self, player_id: Uuid) {
+        let Some(index) = self.order.iter().position(|id| *id == player_id) else {
+            return;
+        };
+        if self.order.len() == 1 {
+            return;
+        }
+        let now = Instant::now();
+        let current = self.current_at(now);
+        self.order.remove(index);
+        let len = self.order.len();
+        if index < self.chooser {
+            self.chooser -= 1;
+        } else if index == self.chooser {
+            self.chooser = match self.round {
+                0 => index % len,
+                _ => (index + len - 1) % len,
+            };
+        }
+        let next = self.order.iter().position(|id| *id == current).unwrap_or(index % len);
+        self.previous = (next + len - 1) % len;
+        self.started = now;
+    }
+
+    /// Passes the turn to the player after the current player
+    pub fn next_turn(&mut self) {
+        let now = Instant::now();
+        self.previous = self.current_index(now);
+        self.started = now;
+    }
+
+    /// Index of the player that has to guess at `now`, the chooser never guesses
+    fn current_index(&self, now: Instant) -> usize {
+        let skipped = match self.timeout.is_zero() {
+            true => 0,
+            false => (now.saturating_duration_since(self.started).as_nanos() / self.timeout.as_nanos()) as usize,
+        };
+        let guessers = self.order.len() - 1;
+        let mut index = self.previous;
+        for _ in 0..=skipped % guessers.max(1) {
+            index = (index + 1) % self.order.len();
+            if index == self.chooser {
+                index = (index + 1) % self.order.len();
+            }
+        }
+        index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use uuid::Uuid;
+
+    use super::Turns;
+
+    #[test]
+    fn test_rotation() {
+        let players = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+        let mut turns = Turns::new(players[0], Duration::ZERO, false);
+        turns.add(players[1]);
+        turns.add(players[2]);
+        turns.start_round();
+        assert_eq!(turns.chooser(), players[0]);
+        assert_eq!(turns.current(), players[1]);
+        turns.next_turn();
+        assert_eq!(turns.current(), players[2]);
+        turns.next_turn();
+        assert_eq!(turns.current(), players[1]);
+        assert_eq!(turns.next_chooser(), players[1]);
+        turns.start_round();
+        assert_eq!(turns.round(), 2);
+        assert_eq!(turns.chooser(), players[1]);
+        assert_eq!(turns.current(), players[2]);
+        turns.next_turn();
+        assert_eq!(turns.current(), players[0]);
+    }
+
+    #[test]
+    fn test_timeout_skips_player() {
+        let players = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+        let mut turns = Turns::new(players[0], Duration::from_millis(100), false);
+        turns.add(players[1]);
+        turns.add(players[2]);
+        turns.start_round();
+        let start = turns.started;
+        assert_eq!(turns.current_at(start), players[1]);
+        assert_eq!(turns.current_at(start + Duration::from_millis(150)), players[2]);
+        assert_eq!(turns.time_left(start + Duration::from_millis(150)), Some(Duration::from_millis(50)));
+        // The chooser is skipped, after the last guesser the first guesser follows again
+        assert_eq!(turns.current_at(start + Duration::from_millis(250)), players[1]);
+    }
+
+    #[test]
+    fn test_remove_player() {
+        let players = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+        let mut turns = Turns::new(players[0], Duration::ZERO, false);
+        players[1..].iter().for_each(|player| turns.add(*player));
+        turns.start_round();
+        turns.next_turn();
+        assert_eq!(turns.current(), players[2]);
+        // The turn stays with the current player when another player leaves
+        turns.remove(players[1]);
+        assert_eq!(turns.current(), players[2]);
+        // The turn is passed on when the current player leaves
+        turns.remove(players[2]);
+        assert_eq!(turns.current(), players[3]);
+        assert_eq!(turns.next_chooser(), players[3]);
+        // The player after the chooser chooses the next word
+        turns.add(players[1]);
+        turns.remove(players[0]);
+        assert_eq!(turns.players(), [players[3], players[1]]);
+        assert_eq!(turns.next_chooser(), players[3]);
+
+        // Before the first round the player after the chooser takes over the role
+        let mut turns = Turns::new(players[0], Duration::ZERO, false);
+        players[1..].iter().for_each(|player| turns.add(*player));
+        turns.remove(players[0]);
+        assert_eq!(turns.chooser(), players[1]);
+    }
+}
@@ -0,0 +1,267 @@
+use std::{collections::HashMap, fmt::Display, fs, io, path::{Path, PathBuf}, sync::{Arc, Mutex, RwLock}, time::SystemTime};
+
+use rand::Rng;
+
+use super::{normalize_word, ALPHABET};
+
+/// Words from which a random word can be chosen for a game.
+///
+/// A words file contains one word per line. Lines that are empty or start with `#` are ignored, whitespace around the
+/// words is removed. The words are converted with [normalize_word](../fn.normalize_word.html) and may only contain
+/// letters from the [ALPHABET](../constant.ALPHABET.html) and `-`.
+#[derive(Debug, Clone, Default)]
+pub struct WordList {
+    /// Name of the list, usually the name of the file from which the list was loaded
+    name: String,
+    words: Vec<String>,
+}
+
+/// Problem in a single line of a words file
+#[derive(Debug, PartialEq, Eq)]
+pub struct LineError {
+    /// Number of the line, starting at 1
+    pub line: usize,
+    /// The content of the line without surrounding whitespace
+    pub content: String,
+    pub kind: LineErrorKind,
+}
+
+/// The different problems a line can have
+#[derive(Debug, PartialEq, Eq)]
+pub enum LineErrorKind {
+    /// The word contains a character that can not be guessed
+    InvalidCharacter(char),
+    /// The word already appeared in the line with the number
+    Duplicate(usize),
+}
+
+impl Display for LineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            LineErrorKind::InvalidCharacter(c) => write!(f, "line {}: \"{}\" contains the invalid character {:?}", self.line, self.content, c),
+            LineErrorKind::Duplicate(first) => write!(f, "line {}: \"{}\" is a duplicate of line {}", self.line, self.content, first),
+        }
+    }
+}
+
+/// Errors that can occur when a words file is loaded
+#[derive(Debug)]
+pub enum WordListError {
+    /// The file could not be read
+    Io(io::Error),
+    /// The file does not contain a single valid word
+    Empty,
+}
+
+impl Display for WordListError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WordListError::Io(err) => write!(f, "unable to read words file: {}", err),
+            WordListError::Empty => write!(f, "words file does not contain any valid words"),
+        }
+    }
+}
+
+impl From<io::Error> for WordListError {
+    fn from(err: io::Error) -> Self {
+        WordListError::Io(err)
+    }
+}
+
+impl WordList {
+    /// Parses the content of a words file.
+    ///
+    /// # Returns
+    /// The list with all valid words and the problems of all lines that where skipped.
+    pub fn parse(name: &str, content: &str) -> (Self, Vec<LineError>) {
+        let mut words = Vec::new();
+        let mut errors = Vec::new();
+        let mut first_occurrence: HashMap<String, usize> = HashMap::new();
+        for (index, line) in content.lines().enumerate() {
+            let line_number = index + 1;
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let word = normalize_word(trimmed);
+            if let Some(c) = word.chars().find(|c| !ALPHABET.contains(*c) && *c != '-') {
+                errors.push(LineError { line: line_number, content: trimmed.to_string(), kind: LineErrorKind::InvalidCharacter(c) });
+                continue;
+            }
+            if let Some(first) = first_occurrence.get(&word) {
+                errors.push(LineError { line: line_number, content: trimmed.to_string(), kind: LineErrorKind::Duplicate(*first) });
+                continue;
+            }
+            first_occurrence.insert(word.clone(), line_number);
+            words.push(word);
+        }
+        (Self { name: name.to_string(), words }, errors)
+    }
+
+    /// Loads the words file, the name of the list is the file name.
+    ///
+    /// # Returns
+    /// The list and the problems of all lines that where skipped, see [parse](#method.parse).
+    ///
+    /// `Err(WordListError)` when the file could not be read or does not contain a valid word
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<(Self, Vec<LineError>), WordListError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)?;
+        let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        let (list, errors) = Self::parse(&name, &content);
+        if list.words.is_empty() {
+            return Err(WordListError::Empty);
+        }
+        Ok((list, errors))
+    }
+
+    /// Name of the list
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// All words in the list
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// The number of words in the list
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// `true` when the list does not contain any words
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+/// A words file together with the words that where loaded from it.
+/// 
+/// The words can be reloaded while the server is running. The new words are loaded before the old words are replaced,
+/// so that readers always see a complete list. When loading fails the previous words are kept.
+pub struct WordSource {
+    path: PathBuf,
+    /// The loaded words, a reload swaps the list so that readers can keep using the previous list without copying it
+    list: RwLock<Arc<WordList>>,
+    /// Modification time of the file when it was loaded the last time
+    modified: Mutex<Option<SystemTime>>,
+}
+
+impl WordSource {
+    /// Loads the words file, lines that contain invalid words are skipped and logged.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, WordListError> {
+        let path = path.as_ref().to_path_buf();
+        let modified = modified(&path);
+        let list = load_logged(&path)?;
+        Ok(Self {
+            path,
+            list: RwLock::new(Arc::new(list)),
+            modified: Mutex::new(modified),
+        })
+    }
+
+    /// Reads the words file again and replaces the words.
+    /// 
+    /// # Returns
+    /// The number of words that have been loaded or the error that occurred, the previous words are kept in that case.
+    pub fn reload(&self) -> Result<usize, WordListError> {
+        let modified = modified(&self.path);
+        let list = load_logged(&self.path)?;
+        let count = list.len();
+        *self.list.write().unwrap() = Arc::new(list);
+        *self.modified.lock().unwrap() = modified;
+        Ok(count)
+    }
+
+    /// Reloads the words file when it has been modified since it was loaded the last time.
+    /// 
+    /// # Returns
+    /// `Ok(Some(usize))` the number of words when the file was reloaded
+    /// 
+    /// `Ok(None)` when the file has not been modified
+    /// 
+    /// `Err(WordListError)` when the file was modified but could not be loaded, the previous words are kept
+    pub fn reload_if_changed(&self) -> Result<Option<usize>, WordListError> {
+        let modified = modified(&self.path);
+        if modified.is_none() || modified == *self.modified.lock().unwrap() {
+            return Ok(None);
+        }
+        let result = self.reload();
+        // Remember the modification time of a broken file as well, so that the error is only reported once
+        *self.modified.lock().unwrap() = modified;
+        result.map(Some)
+    }
+
+    /// Returns the words that are currently loaded, a later reload does not change the returned list
+    pub fn list(&self) -> Arc<WordList> {
+        Arc::clone(&self.list.read().unwrap())
+    }
+
+    /// The number of words that are currently loaded
+    pub fn len(&self) -> usize {
+        self.list.read().unwrap().len()
+    }
+
+    /// `true` when no words are loaded, a words file without words is never loaded
+    pub fn is_empty(&self) -> bool {
+        self.list.read().unwrap().is_empty()
+    }
+
+    /// Returns a random word
+    pub fn random_word(&self) -> String {
+        let list = self.list.read().unwrap();
+        let number = rand::thread_rng().gen_range(0..list.len());
+        list.words()[number].clone()
+    }
+
+    /// The file from which the words are loaded
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Returns the time at which the file was modified the last time
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+/// Loads the words file and logs all lines that are skipped
+fn load_logged(path: &Path) -> Result<WordList, WordListError> {
+    let (list, errors) = WordList::load(path)?;
+    for err in errors {
+        warn!("{}: {}", path.display(), err);
+    }
+    Ok(list)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LineErrorKind, WordList, WordSource};
+
+    #[test]
+    fn test_parse() {
+        let content = "# Comment\r\nHund\r\n\r\n  Brücke \nhund\nAuto1\nStraße\n\u{a0}Holz-Brücke\u{a0}\n";
+        let (list, errors) = WordList::parse("test", content);
+        assert_eq!(list.words(), &["HUND", "BRUECKE", "STRASSE", "HOLZ-BRUECKE"]);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].line, 5);
+        assert_eq!(errors[0].kind, LineErrorKind::Duplicate(2));
+        assert_eq!(errors[1].line, 6);
+        assert_eq!(errors[1].kind, LineErrorKind::InvalidCharacter('1'));
+    }
+
+    #[test]
+    fn test_failed_reload_keeps_words() {
+        let path = std::env::temp_dir().join(format!("hangman-words-{}.txt", uuid::Uuid::new_v4()));
+        std::fs::write(&path, "Hund\nKatze\n").unwrap();
+        let source = WordSource::load(&path).unwrap();
+        std::fs::write(&path, "# only a comment\n").unwrap();
+        assert!(source.reload().is_err());
+        assert_eq!(source.list().words(), &["HUND", "KATZE"]);
+        std::fs::write(&path, "Maus\n").unwrap();
+        assert_eq!(source.reload().unwrap(), 1);
+        assert_eq!(source.random_word(), "MAUS");
+        std::fs::remove_file(&path).unwrap();
+    }
+}
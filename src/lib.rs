@@ -0,0 +1,98 @@
+use std::{collections::HashMap, sync::{Arc, RwLock}, time::{Duration, Instant}};
+
+use events::Events;
+use game::{word_list::WordSource, GameManager};
+use metrics::{Metrics, RequestTimer};
+use rate_limit::{Limit, RateLimiter};
+use request_data::{AdminConfig, ServerStart};
+use rocket::{fs::{FileServer, relative}, Config, Rocket, Build};
+use rocket_okapi::{get_openapi_route, settings::OpenApiSettings, swagger_ui::{make_swagger_ui, SwaggerUIConfig}};
+use session::{SessionConfig, DEFAULT_SESSION_LIFETIME};
+use timer_watcher::TimerWatcher;
+use words_watcher::WordsWatcher;
+
+/// Live events of the games that are delivered to the players as server-sent events
+mod events;
+/// ASCII drawing of the gallows, used by the terminal clients
+pub mod gallows;
+/// The underlying game, contains logic and components that are required to run the game
+pub mod game;
+/// All paths for which a request handler is registered.
+/// 
+/// All requests that interact with games require a player authentication that is set when the player registers for a game.
+/// This authentication is done by setting a cookie that is checked each time the player interacts with the server endpoints.
+/// When the cookie is invalid or not set the connection is refused.
+pub mod paths;
+/// Prometheus metrics about the games and requests
+pub mod metrics;
+/// Rate limits for registrations, guesses and chat messages
+pub mod rate_limit;
+/// Different data types that are required to process requests
+mod request_data;
+/// Signed session tokens that authenticate the players
+pub mod session;
+/// Ends games whose time has run out
+mod timer_watcher;
+/// Reloads the words file when it changes
+mod words_watcher;
+
+#[macro_use] extern crate rocket;
+
+/// Builds the server with all routes and the managed game state
+/// 
+/// The admin api is enabled by setting `admin_token` in `Rocket.toml` or the environment variable `ROCKET_ADMIN_TOKEN`.
+/// 
+/// Session tokens are signed with `session_secret`, a random secret is used when it is not set. They expire after
+/// `session_lifetime` seconds (default one day), `secure_cookies` should be enabled when the server is reached by https.
+/// 
+/// Registrations and guesses are rate limited per client, the limits can be changed in the `rate_limits` table. A client
+/// can have `max_games_per_client` games at the same time (default 5), further registrations are rejected.
+/// 
+/// The OpenAPI specification of all endpoints is served at `/api/openapi.json` and can be viewed at `/api/docs`.
+/// 
+/// The words file is checked for changes every `words_reload_interval` seconds (default 5), `0` disables the check.
+/// 
+/// The time limits of all games are checked and the bots move every `timer_check_interval` seconds (default 1), `0`
+/// disables the check and the bots. The limits are still enforced when a game is accessed.
+pub fn build_rocket() -> Rocket<Build> {
+    build_rocket_with_words(WordSource::load("words.txt").expect("Unable to read words file!"))
+}
+
+/// Builds the server like [build_rocket] but chooses the words of new games from `words` instead of `words.txt`
+pub fn build_rocket_with_words(words: WordSource) -> Rocket<Build> {
+    let config = Config::figment().merge(("port", 11511));
+    let admin_token = config.extract_inner::<String>("admin_token").ok().filter(|token| !token.is_empty());
+    let words_reload_interval = config.extract_inner::<u64>("words_reload_interval").unwrap_or(5);
+    let timer_check_interval = config.extract_inner::<u64>("timer_check_interval").unwrap_or(1);
+    let session_config = SessionConfig::new(
+        config.extract_inner::<String>("session_secret").ok().filter(|secret| !secret.is_empty()),
+        Duration::from_secs(config.extract_inner::<u64>("session_lifetime").unwrap_or(DEFAULT_SESSION_LIFETIME)),
+        config.extract_inner::<bool>("secure_cookies").unwrap_or(false),
+    );
+    let rate_limiter = RateLimiter::new(
+        config.extract_inner::<HashMap<String, Limit>>("rate_limits").unwrap_or_default(),
+        config.extract_inner::<usize>("max_games_per_client").unwrap_or(5),
+    );
+    let (api_routes, spec) = paths::api_routes();
+    rocket::custom(config)
+        .mount("/", FileServer::from(relative!("web")))
+        .mount("/", routes![paths::singleplayer])
+        .mount("/", api_routes)
+        .mount("/api", vec![get_openapi_route(spec, &OpenApiSettings::default())])
+        .mount("/api/docs", make_swagger_ui(&SwaggerUIConfig {
+            url: String::from("/api/openapi.json"),
+            ..Default::default()
+        }))
+        .manage(Arc::new(RwLock::new(GameManager::with_words(words))))
+        .manage(AdminConfig { token: admin_token })
+        .manage(Metrics::new())
+        .manage(Events::new())
+        .manage(ServerStart(Instant::now()))
+        .manage(session_config)
+        .manage(rate_limiter)
+        .register("/", catchers![rate_limit::too_many_requests])
+        .attach(RequestTimer)
+        .attach(paths::DeprecationHeader)
+        .attach(WordsWatcher { interval: Duration::from_secs(words_reload_interval) })
+        .attach(TimerWatcher { interval: Duration::from_secs(timer_check_interval) })
+}
@@ -0,0 +1,266 @@
+use std::{collections::BTreeMap, fmt::Write, sync::{atomic::{AtomicU64, Ordering}, Arc, Mutex, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard}, time::{Duration, Instant}};
+
+use rocket::{fairing::{Fairing, Info, Kind}, Data, Request, Response};
+
+use crate::game::{base_game::GameResults, guess::GUESS_OUTCOMES};
+
+/// Upper bounds of the histogram buckets in seconds
+const BUCKETS: [f64; 11] = [0.0001, 0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.5, 1.0];
+
+
+/// Histogram with the fixed [BUCKETS] that counts durations
+pub struct Histogram {
+    /// Number of observations per bucket, the last entry is the `+Inf` bucket
+    buckets: [AtomicU64; BUCKETS.len() + 1],
+    /// Sum of all observations in nanoseconds
+    sum: AtomicU64,
+    count: AtomicU64,
+}
+
+impl Histogram {
+    pub fn new() -> Self {
+        Self {
+            buckets: Default::default(),
+            sum: AtomicU64::new(0),
+            count: AtomicU64::new(0),
+        }
+    }
+
+    /// Records the duration
+    pub fn observe(&self, duration: Duration) {
+        let seconds = duration.as_secs_f64();
+        let bucket = BUCKETS.iter().position(|bound| seconds <= *bound).unwrap_or(BUCKETS.len());
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        self.sum.fetch_add(duration.as_nanos() as u64, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Writes the histogram in the prometheus text format, `labels` are added to each sample
+    fn write(&self, out: &mut String, name: &str, labels: &str) {
+        let separator = match labels.is_empty() {
+            true => "",
+            false => ",",
+        };
+        let mut cumulative = 0;
+        for (index, bound) in BUCKETS.iter().enumerate() {
+            cumulative += self.buckets[index].load(Ordering::Relaxed);
+            let _ = writeln!(out, "{}_bucket{{{}{}le=\"{}\"}} {}", name, labels, separator, bound, cumulative);
+        }
+        cumulative += self.buckets[BUCKETS.len()].load(Ordering::Relaxed);
+        let _ = writeln!(out, "{}_bucket{{{}{}le=\"+Inf\"}} {}", name, labels, separator, cumulative);
+        let labels = match labels.is_empty() {
+            true => String::new(),
+            false => format!("{{{}}}", labels),
+        };
+        let _ = writeln!(out, "{}_sum{} {}", name, labels, self.sum.load(Ordering::Relaxed) as f64 / 1e9);
+        let _ = writeln!(out, "{}_count{} {}", name, labels, self.count.load(Ordering::Relaxed));
+    }
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Number of guesses per outcome, see [GUESS_OUTCOMES](../game/guess/constant.GUESS_OUTCOMES.html).
+///
+/// The counter is shared with the [TimerWatcher](../timer_watcher/struct.TimerWatcher.html) that records the guesses of
+/// the bots.
+#[derive(Default)]
+pub struct GuessCounter([AtomicU64; GUESS_OUTCOMES.len()]);
+
+impl GuessCounter {
+    /// Records the result of [Game::guess](../game/base_game/struct.Game.html#method.guess)
+    pub fn record(&self, result: i32) {
+        if let Some(counter) = usize::try_from(result - 1).ok().and_then(|index| self.0.get(index)) {
+            counter.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Counters and histograms that are exposed on the `/metrics` endpoint.
+///
+/// One `Metrics` instance is managed by rocket, the request latency is recorded by the [RequestTimer] fairing.
+#[derive(Default)]
+pub struct Metrics {
+    /// Number of games that have been registered
+    registrations: AtomicU64,
+    /// Number of guesses per outcome of the players and the bots
+    guesses: Arc<GuessCounter>,
+    /// Number of ended games that where deleted because the [MAX_ACTIVE_GAMES](../game/constant.MAX_ACTIVE_GAMES.html) limit was reached
+    evictions: AtomicU64,
+    /// Time that was spent waiting for the game manager lock
+    lock_wait: Histogram,
+    /// Duration of the requests per method and endpoint
+    request_duration: Mutex<BTreeMap<(String, String), Histogram>>,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that a game was registered.
+    ///
+    /// `evicted` is set when the oldest ended game had to be deleted to make space for the new game.
+    pub fn record_registration(&self, evicted: bool) {
+        self.registrations.fetch_add(1, Ordering::Relaxed);
+        if evicted {
+            self.evictions.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Records the result of [Game::guess](../game/base_game/struct.Game.html#method.guess)
+    pub fn record_guess(&self, result: i32) {
+        self.guesses.record(result);
+    }
+
+    /// # Returns
+    /// The counter of the guesses, used to record guesses outside of a request
+    pub fn guess_counter(&self) -> Arc<GuessCounter> {
+        Arc::clone(&self.guesses)
+    }
+
+    /// Acquires the write lock and records how long it took
+    pub fn write<'a, T>(&self, lock: &'a RwLock<T>) -> RwLockWriteGuard<'a, T> {
+        let start = Instant::now();
+        let guard = lock.write().unwrap();
+        self.lock_wait.observe(start.elapsed());
+        guard
+    }
+
+    /// Acquires the read lock and records how long it took
+    pub fn read<'a, T>(&self, lock: &'a RwLock<T>) -> RwLockReadGuard<'a, T> {
+        let start = Instant::now();
+        let guard = lock.read().unwrap();
+        self.lock_wait.observe(start.elapsed());
+        guard
+    }
+
+    /// Records the duration of a request.
+    ///
+    /// The histograms stay usable when a thread panicked while holding the lock, a panic only loses the observation
+    /// that was recorded at that time.
+    fn record_request(&self, method: &str, endpoint: &str, duration: Duration) {
+        let mut request_duration = self.request_duration.lock().unwrap_or_else(PoisonError::into_inner);
+        request_duration.entry((method.to_string(), endpoint.to_string())).or_default().observe(duration);
+    }
+
+    /// Renders all metrics in the prometheus text format.
+    ///
+    /// `active_games`, `max_active_games` and the `results` of the games are read from the game manager by the caller.
+    pub fn render(&self, active_games: usize, max_active_games: usize, results: &GameResults) -> String {
+        let mut out = String::new();
+        write_metric(&mut out, "hangman_active_games", "gauge", "Number of games that are currently managed", active_games);
+        write_metric(&mut out, "hangman_max_active_games", "gauge", "Number of games after which ended games are deleted and new games are rejected", max_active_games);
+        write_metric(&mut out, "hangman_registrations_total", "counter", "Number of games that have been registered", self.registrations.load(Ordering::Relaxed));
+        write_metric(&mut out, "hangman_evictions_total", "counter", "Number of ended games that have been deleted because the game limit was reached", self.evictions.load(Ordering::Relaxed));
+
+        let _ = writeln!(out, "# HELP hangman_guesses_total Number of guesses by outcome");
+        let _ = writeln!(out, "# TYPE hangman_guesses_total counter");
+        for (outcome, counter) in GUESS_OUTCOMES.iter().zip(&self.guesses.0) {
+            let _ = writeln!(out, "hangman_guesses_total{{outcome=\"{}\"}} {}", outcome, counter.load(Ordering::Relaxed));
+        }
+        write_metric(&mut out, "hangman_wins_total", "counter", "Number of games that have been won", results.wins());
+        write_metric(&mut out, "hangman_losses_total", "counter", "Number of games that have been lost", results.losses());
+
+        let _ = writeln!(out, "# HELP hangman_lock_wait_seconds Time spent waiting for the game manager lock");
+        let _ = writeln!(out, "# TYPE hangman_lock_wait_seconds histogram");
+        self.lock_wait.write(&mut out, "hangman_lock_wait_seconds", "");
+
+        let _ = writeln!(out, "# HELP hangman_request_duration_seconds Duration of the requests by endpoint");
+        let _ = writeln!(out, "# TYPE hangman_request_duration_seconds histogram");
+        for ((method, endpoint), histogram) in self.request_duration.lock().unwrap_or_else(PoisonError::into_inner).iter() {
+            histogram.write(&mut out, "hangman_request_duration_seconds", &format!("method=\"{}\",endpoint=\"{}\"", method, endpoint));
+        }
+        out
+    }
+}
+
+/// Writes a metric with a single sample
+fn write_metric(out: &mut String, name: &str, kind: &str, help: &str, value: impl std::fmt::Display) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+    let _ = writeln!(out, "{} {}", name, value);
+}
+
+/// Fairing that records the duration of each request in the managed [Metrics].
+///
+/// Requests are grouped by the uri of the route that handled them, requests that did not match a route are grouped as `unmatched`.
+pub struct RequestTimer;
+
+/// Time at which the request was received, stored in the request local cache
+struct RequestStart(Option<Instant>);
+
+#[rocket::async_trait]
+impl Fairing for RequestTimer {
+    fn info(&self) -> Info {
+        Info {
+            name: "Request timer",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _data: &mut Data<'_>) {
+        request.local_cache(|| RequestStart(Some(Instant::now())));
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, _response: &mut Response<'r>) {
+        let Some(start) = request.local_cache(|| RequestStart(None)).0 else {
+            return;
+        };
+        let Some(metrics) = request.rocket().state::<Metrics>() else {
+            return;
+        };
+        let endpoint = request.route().map(|route| route.uri.to_string()).unwrap_or_else(|| String::from("unmatched"));
+        metrics.record_request(request.method().as_str(), &endpoint, start.elapsed());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::game::base_game::GameResults;
+
+    use super::Metrics;
+
+    #[test]
+    fn test_render() {
+        let metrics = Metrics::new();
+        metrics.record_registration(true);
+        metrics.record_guess(1);
+        metrics.record_guess(3);
+        metrics.record_guess(42);
+        metrics.record_request("GET", "/api/lives", Duration::from_millis(2));
+        let results = GameResults::default();
+        results.record(true);
+        results.record(false);
+        results.record(false);
+        let out = metrics.render(1, 1000, &results);
+        assert!(out.contains("hangman_active_games 1\n"));
+        assert!(out.contains("hangman_evictions_total 1\n"));
+        assert!(out.contains("hangman_guesses_total{outcome=\"wrong\"} 1\n"));
+        assert!(out.contains("hangman_wins_total 1\n"));
+        assert!(out.contains("hangman_losses_total 2\n"));
+        assert!(out.contains("hangman_request_duration_seconds_bucket{method=\"GET\",endpoint=\"/api/lives\",le=\"0.001\"} 0\n"));
+        assert!(out.contains("hangman_request_duration_seconds_bucket{method=\"GET\",endpoint=\"/api/lives\",le=\"0.0025\"} 1\n"));
+        assert!(out.contains("hangman_request_duration_seconds_count{method=\"GET\",endpoint=\"/api/lives\"} 1\n"));
+    }
+
+    #[test]
+    fn test_poisoned_request_duration() {
+        let metrics = Metrics::new();
+        std::thread::scope(|scope| {
+            let _ = scope.spawn(|| {
+                let _guard = metrics.request_duration.lock().unwrap();
+                panic!("Poison the lock");
+            }).join();
+        });
+        assert!(metrics.request_duration.is_poisoned());
+        metrics.record_request("GET", "/api/lives", Duration::from_millis(2));
+        let out = metrics.render(0, 1000, &GameResults::default());
+        assert!(out.contains("hangman_request_duration_seconds_count{method=\"GET\",endpoint=\"/api/lives\"} 1\n"));
+    }
+}
@@ -0,0 +1,101 @@
+use std::sync::{Arc, RwLock};
+
+use rocket::{http::Status, serde::json::Json, State};
+use rocket_okapi::openapi;
+use uuid::Uuid;
+
+use crate::{game::{base_game::Game, GameManager, MAX_ACTIVE_GAMES}, metrics::Metrics, request_data::{AdminAuth, GameDetails, GameInfo, ManagerStats}};
+
+/// Lists all active games, the oldest game comes first
+#[openapi(tag = "Admin")]
+#[get("/api/admin/games")]
+pub fn games(game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, _admin: AdminAuth) -> Json<Vec<GameInfo>> {
+    let game_manager = metrics.read(game_manager);
+    Json(game_manager.games().into_iter().map(game_info).collect())
+}
+
+/// Details of a single game including the word and all guesses
+/// 
+/// # Return
+/// `404` when the game does not exist
+#[openapi(tag = "Admin")]
+#[get("/api/admin/games/<id>")]
+pub fn game(game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, _admin: AdminAuth, id: Uuid) -> Result<Json<GameDetails>, Status> {
+    let mut game_manager = metrics.write(game_manager);
+    let game = game_manager.game_by_id(id).ok_or(Status::NotFound)?;
+    Ok(Json(GameDetails {
+        info: game_info(game),
+        word: game.solution(),
+        game_string: game.game_string(),
+        history: game.history().to_vec(),
+    }))
+}
+
+/// Ends the game immediately, the game is marked as lost but not deleted.
+/// 
+/// # Return
+/// `404` when the game does not exist
+#[openapi(tag = "Admin")]
+#[post("/api/admin/games/<id>/end")]
+pub fn end_game(game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, _admin: AdminAuth, id: Uuid) -> Result<Json<GameInfo>, Status> {
+    let mut game_manager = metrics.write(game_manager);
+    let game = game_manager.game_by_id(id).ok_or(Status::NotFound)?;
+    game.force_end();
+    Ok(Json(game_info(game)))
+}
+
+/// Deletes the game and all players that are assigned to it
+/// 
+/// # Return
+/// `404` when the game does not exist
+#[openapi(tag = "Admin")]
+#[delete("/api/admin/games/<id>")]
+pub fn delete_game(game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, _admin: AdminAuth, id: Uuid) -> Status {
+    match metrics.write(game_manager).delete_game_by_id(id) {
+        true => Status::NoContent,
+        false => Status::NotFound,
+    }
+}
+
+/// Reads the words file again, running games keep their word
+/// 
+/// # Return
+/// The new counts of the game manager or `500` when the words file could not be read, the previous words are kept in that case.
+#[openapi(tag = "Admin")]
+#[post("/api/admin/reload_words")]
+pub fn reload_words(game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, _admin: AdminAuth) -> Result<Json<ManagerStats>, Status> {
+    // The words are loaded without holding the game manager lock so that running games are not blocked
+    let words = metrics.read(game_manager).words();
+    if let Err(err) = words.reload() {
+        error!("Unable to reload words file, keeping the previous words: {}", err);
+        return Err(Status::InternalServerError);
+    }
+    Ok(Json(manager_stats(&metrics.read(game_manager))))
+}
+
+/// Number of active games compared to the limit and the number of loaded words
+#[openapi(tag = "Admin")]
+#[get("/api/admin/stats")]
+pub fn stats(game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, _admin: AdminAuth) -> Json<ManagerStats> {
+    Json(manager_stats(&metrics.read(game_manager)))
+}
+
+/// Collects the overview of the game
+fn game_info(game: &Game) -> GameInfo {
+    GameInfo {
+        game_id: game.game_id(),
+        age: game.age().as_secs(),
+        lives: game.lives(),
+        status: String::from(game.status()),
+        players: game.player_ids().len(),
+    }
+}
+
+/// Collects the counts of the game manager
+fn manager_stats(game_manager: &GameManager) -> ManagerStats {
+    ManagerStats {
+        active_games: game_manager.games().len(),
+        max_active_games: MAX_ACTIVE_GAMES,
+        words: game_manager.word_count(),
+    }
+}
@@ -0,0 +1,59 @@
+use std::sync::{Arc, RwLock};
+
+use rocket::{http::Status, serde::json::Json, State};
+use rocket_okapi::openapi;
+
+use crate::{game::{GameManager, MAX_ACTIVE_GAMES}, request_data::{Health, Readiness, ServerStart}};
+
+/// Liveness probe
+/// 
+/// # Return
+/// `200` when the server is healthy, `503` when the game manager lock is poisoned because a request handler panicked.
+/// The body contains the uptime and the state of the lock in both cases.
+#[openapi(tag = "Health")]
+#[get("/healthz")]
+pub fn healthz(game_manager: &State<Arc<RwLock<GameManager>>>, start: &State<ServerStart>) -> (Status, Json<Health>) {
+    let health = health(game_manager, start);
+    (status(health.lock == "ok"), Json(health))
+}
+
+/// Readiness probe
+/// 
+/// # Return
+/// `200` when new games can be registered, `503` when the lock is poisoned or no words are loaded.
+/// The body contains the number of words per dictionary, the persistence backend and the game capacity.
+#[openapi(tag = "Health")]
+#[get("/readyz")]
+pub fn readyz(game_manager: &State<Arc<RwLock<GameManager>>>, start: &State<ServerStart>) -> (Status, Json<Readiness>) {
+    let health = health(game_manager, start);
+    // The counts can still be read when the lock is poisoned
+    let game_manager = game_manager.read().unwrap_or_else(|err| err.into_inner());
+    let dictionaries = game_manager.dictionaries().into_iter().collect();
+    let words = game_manager.word_count();
+    let readiness = Readiness {
+        ready: health.lock == "ok" && words > 0,
+        health,
+        dictionaries,
+        persistence: String::from("memory"),
+        active_games: game_manager.games().len(),
+        max_active_games: MAX_ACTIVE_GAMES,
+    };
+    (status(readiness.ready), Json(readiness))
+}
+
+/// Checks the game manager lock and calculates the uptime
+fn health(game_manager: &RwLock<GameManager>, start: &ServerStart) -> Health {
+    let lock_ok = !game_manager.is_poisoned();
+    Health {
+        status: String::from(if lock_ok { "ok" } else { "unhealthy" }),
+        uptime: start.0.elapsed().as_secs(),
+        lock: String::from(if lock_ok { "ok" } else { "poisoned" }),
+    }
+}
+
+fn status(ok: bool) -> Status {
+    match ok {
+        true => Status::Ok,
+        false => Status::ServiceUnavailable,
+    }
+}
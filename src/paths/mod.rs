@@ -0,0 +1,349 @@
+
+use std::{net::IpAddr, sync::{Arc, RwLock}, path::Path};
+use crate::{request_data::{PlayerAuth, PlayerAuthError, PlayerToken, RegistrationData, RematchRequest, SeriesView, TOKEN_COOKIE}, game::{guess::{Guess, GuessError}, GameManager, MAX_ACTIVE_GAMES, MAX_LIVES}, metrics::Metrics, rate_limit::{Guesses, RateLimit, RateLimiter, Registrations, WithRetryAfter, GAME_LIMIT_RETRY_AFTER}, session::SessionConfig};
+use rocket::{fairing::{Fairing, Info, Kind}, http::{ContentType, CookieJar, Cookie, Status}, response::status::Custom, serde::json::Json, Request, Response, Route, State, fs::NamedFile};
+
+use rocket_okapi::{okapi::openapi3::OpenApi, openapi, openapi_get_routes_spec, settings::OpenApiSettings};
+
+use self::utils::{game_by_player_auth, register_player, rematch_status, series_view};
+
+/// Routes of the admin api, these are authenticated by the admin token instead of the player cookie
+pub mod admin;
+/// Liveness and readiness probes
+pub mod health;
+/// Resource oriented api that replaces the flat endpoints in this module
+pub mod v2;
+
+/// Endpoints of the first version of the api, they are kept for the singleplayer page but are deprecated in favor of [v2]
+pub const DEPRECATED_ENDPOINTS: [&str; 11] = ["/api/register", "/api/guess", "/api/lives", "/api/max_lives", "/api/game_string", "/api/word", "/api/delete_game", "/api/guessed_letters", "/api/teammates", "/api/game_id", "/api/registered"];
+
+/// Collects all routes of the api together with the OpenAPI specification that is generated from them.
+/// 
+/// The endpoints of the first version are marked as deprecated in the specification.
+pub fn api_routes() -> (Vec<Route>, OpenApi) {
+    let settings = OpenApiSettings::default();
+    let (routes, mut spec) = openapi_get_routes_spec![settings:
+        register, registered, submit_char, rematch, lives, max_lives, game_string, word, guessed_letters, teammates, game_id, delete_game, metrics,
+        v2::create_game, v2::create_evil_game, v2::game, v2::submit_guess, v2::rematch, v2::delete_game, v2::players, v2::create_race, v2::join_race, v2::race,
+        v2::create_turn_game, v2::join_game, v2::choose_word, v2::create_time_attack,
+        v2::hint, v2::add_race_bot, v2::add_game_bot,
+        v2::create_spectator_link, v2::spectate, v2::set_spectating,
+        v2::chat, v2::send_message, v2::events,
+        admin::games, admin::game, admin::end_game, admin::delete_game, admin::reload_words, admin::stats,
+        health::healthz, health::readyz,
+    ];
+    spec.info.title = String::from("hangman_rs_web");
+    spec.info.version = String::from(env!("CARGO_PKG_VERSION"));
+    for (path, item) in spec.paths.iter_mut() {
+        if DEPRECATED_ENDPOINTS.contains(&path.as_str()) {
+            for operation in [&mut item.get, &mut item.post, &mut item.delete].into_iter().flatten() {
+                operation.deprecated = true;
+            }
+        }
+    }
+    (routes, spec)
+}
+
+/// Fairing that marks the responses of the [DEPRECATED_ENDPOINTS] with the `Deprecation` header and links the v2 api
+pub struct DeprecationHeader;
+
+#[rocket::async_trait]
+impl Fairing for DeprecationHeader {
+    fn info(&self) -> Info {
+        Info {
+            name: "Deprecation header",
+            kind: Kind::Response,
+        }
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let deprecated = request.route().map(|route| DEPRECATED_ENDPOINTS.contains(&route.uri.to_string().as_str())).unwrap_or(false);
+        if deprecated {
+            response.set_raw_header("Deprecation", "true");
+            response.set_raw_header("Link", "</api/v2/games>; rel=\"successor-version\"");
+        }
+    }
+}
+
+/// Returns the singleplayer html page
+#[openapi(skip)]
+#[get("/singleplayer")]
+pub async fn singleplayer() -> Option<NamedFile> {
+    NamedFile::open(Path::new("web/singleplayer/singleplayer.html")).await.ok()
+}
+
+/// Register a new player to the server
+/// 
+/// A new cookie is set that will be used to authorize the user against the server in subsequent request to endpoints required to play the game.
+/// Clients that can not use cookies can send the returned token in the `Authorization: Bearer <token>` header instead.
+/// 
+/// When the request contains a valid token that player leaves the previous game, so that the old token can not be used
+/// anymore. The game is deleted when nobody else is left in it.
+/// 
+/// The registrations are rate limited per client and each client can only have a limited number of games, see
+/// [RateLimiter](../rate_limit/struct.RateLimiter.html).
+/// 
+/// This cookie is deleted when the game ends.
+/// 
+/// # Requires
+/// Nothing
+/// 
+/// # Return
+/// The token that is required to authenticate subsequent requests to the server.
+/// 
+/// `429` when the client already has the maximum number of games or the server is full
+#[openapi(tag = "Game v1")]
+#[post("/api/register")]
+#[allow(clippy::too_many_arguments)]
+pub fn register(_limit: RateLimit<Registrations>, client: Option<IpAddr>, cookies: &CookieJar<'_>, old_token: Result<PlayerToken, PlayerAuthError>, game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, session_config: &State<SessionConfig>, rate_limiter: &State<RateLimiter>) -> Result<Json<RegistrationData>, WithRetryAfter<Custom<Json<String>>>> {
+    let (_result, token) = register_player(&mut metrics.write(game_manager), metrics, session_config, rate_limiter, client, old_token)
+        .map_err(|err| WithRetryAfter::new(Custom(Status::TooManyRequests, Json(err.to_string())), GAME_LIMIT_RETRY_AFTER))?;
+    cookies.add(session_config.cookie(token.clone()));
+    Ok(Json(RegistrationData { token }))
+}
+
+/// Submits a letter/word to the game
+/// 
+/// # Requires
+/// The user needs to send a letter/word in the post request body.
+/// 
+/// # Return
+/// The result of [Game::guess](../game/base_game/struct.Game.html#method.guess)
+/// 
+/// `400` with the reason as json string when the guess is invalid, see [Guess](../game/guess/enum.Guess.html). No life is lost in that case.
+/// 
+/// `409` with the reason as json string when it is not the turn of the player in a turn-based game
+#[openapi(tag = "Game v1")]
+#[post("/api/guess", data = "<guess>")]
+pub fn submit_char(_limit: RateLimit<Guesses>, game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, player_auth: PlayerAuth, guess: Json<String>) -> Result<(ContentType, String), Custom<Json<String>>> {
+    let guess: Guess = guess.0.parse().map_err(|err: GuessError| Custom(Status::BadRequest, Json(err.to_string())))?;
+    let result = metrics.write(game_manager).guess(player_auth.game_id, player_auth.player_id, guess).map_err(|err| Custom(Status::Conflict, Json(err.to_string())))?;
+    metrics.record_guess(result);
+    Ok((ContentType::Text, result.to_string()))
+}
+
+/// Starts a new round with a new word for the players of the game once it has ended
+/// 
+/// The game id and the tokens stay the same, the results of the rounds are counted in a series. The body can contain
+/// `best_of` to start a new best-of series.
+/// # Return
+/// The series of the game
+/// 
+/// `400` with the reason as json string when the length of the series is invalid
+/// 
+/// `409` with the reason as json string when the game has not ended yet, is turn-based or part of a race
+#[openapi(tag = "Game v1")]
+#[post("/api/rematch", data = "<rematch>")]
+pub fn rematch(game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, player_auth: PlayerAuth, rematch: Option<Json<RematchRequest>>) -> Result<Json<SeriesView>, Custom<Json<String>>> {
+    let mut game_manager = metrics.write(game_manager);
+    game_manager.rematch(player_auth.game_id, rematch.and_then(|rematch| rematch.best_of)).map_err(|err| Custom(rematch_status(&err), Json(err.to_string())))?;
+    let game = game_by_player_auth(&mut game_manager, player_auth).unwrap();
+    Ok(Json(series_view(game)))
+}
+
+/// The amount of lives left
+/// 
+/// See [Game::lives](../game/base_game/struct.Game.html#method.lives)
+#[openapi(tag = "Game v1")]
+#[get("/api/lives")]
+pub fn lives(game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, player_auth: PlayerAuth) -> (ContentType, String) {
+    let mut game_manager = metrics.write(game_manager);
+    let game = game_by_player_auth(&mut game_manager, player_auth).unwrap();
+    (ContentType::Text, game.lives().to_string())
+}
+
+/// The amount of lives a game starts with
+/// 
+/// Used by the frontend to spread the hangman drawing over all lives.
+#[openapi(tag = "Game v1")]
+#[get("/api/max_lives")]
+pub fn max_lives() -> (ContentType, String) {
+    (ContentType::Text, MAX_LIVES.to_string())
+}
+
+/// The game string
+/// 
+/// See [Game::game_string](../game/base_game/struct.Game.html#method.game_string)
+#[openapi(tag = "Game v1")]
+#[get("/api/game_string")]
+pub fn game_string(game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, player_auth: PlayerAuth) -> (ContentType, String) {
+    let mut game_manager = metrics.write(game_manager);
+    let game = game_by_player_auth(&mut game_manager, player_auth).unwrap();
+    (ContentType::Text, game.game_string())
+}
+
+/// The correct word if the game has ended
+/// 
+/// See [Game::word](../game/base_game/struct.Game.html#method.word)
+#[openapi(tag = "Game v1")]
+#[get("/api/word")]
+pub fn word(game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, player_auth: PlayerAuth) -> (ContentType, String) {
+    let mut game_manager = metrics.write(game_manager);
+    let game = game_by_player_auth(&mut game_manager, player_auth).unwrap();
+    let ret = match game.word() {
+        Some(word) => word,
+        None => String::from("Unable to return word: Game has to end first!"),
+    };
+    (ContentType::Text, ret)
+}
+
+/// Delete the game the player is playing in
+/// 
+/// Deprecated because a GET request should not change the state of the server, use `DELETE /api/v2/games/<id>` instead.
+/// 
+/// This removes the cookie that is used to authenticate the player against the server and completely delete the game from the server.
+/// 
+/// # Warning
+/// The game will be deleted directly, the player will not have to confirm that the game should be deleted!
+#[openapi(tag = "Game v1")]
+#[get("/api/delete_game")]
+pub fn delete_game(cookies: &CookieJar<'_>, game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, player_auth: PlayerAuth) -> (ContentType, String) {
+    // I know that in this way the user does not have to confirm the deletion of the game.
+    let mut game_manager = metrics.write(game_manager);
+    game_manager.delete_game(player_auth.player_id);
+    // Delete cookie
+    cookies.remove(Cookie::named(TOKEN_COOKIE));
+    // Send event to users
+    (ContentType::Text, String::from("Game has been deleted, users have been reset"))
+}
+
+/// All guessed letters
+/// 
+/// See [Game::guessed_letters](../game/base_game/struct.Game.html#method.guessed_letters)
+#[openapi(tag = "Game v1")]
+#[get("/api/guessed_letters")]
+pub fn guessed_letters(game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, player_auth: PlayerAuth) -> (ContentType, String) {
+    let mut game_manager = metrics.write(game_manager);
+    let game = game_by_player_auth(&mut game_manager, player_auth).unwrap();
+    (ContentType::Text, game.guessed_letters())
+}
+
+/// The names of the teammate
+/// 
+/// See [Game::teammates](../game/base_game/struct.Game.html#method.teammates)
+#[openapi(tag = "Game v1")]
+#[get("/api/teammates")]
+pub fn teammates(game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, player_auth: PlayerAuth) -> (ContentType, String) {
+    let mut game_manager = metrics.write(game_manager);
+    let game = game_by_player_auth(&mut game_manager, player_auth).unwrap();
+    (ContentType::Text, game.teammates(player_auth.player_id))
+}
+
+/// The game id to which the player is registered
+#[openapi(tag = "Game v1")]
+#[get("/api/game_id")]
+pub fn game_id(player_auth: PlayerAuth) -> (ContentType, String) {
+    (ContentType::Text, player_auth.game_id.to_string()) 
+}
+
+/// Check if the submitted token is valid and the user is assigned to a game
+/// 
+/// # Return
+/// `false` when the token is missing or invalid
+/// 
+/// `playing` when the user exists and is playing in a game
+/// 
+/// `won` if the game has ended and was won but is not yet deleted
+/// 
+/// `lost` if the game has ended and was lost but is not yet deleted
+#[openapi(tag = "Game v1")]
+#[get("/api/registered")]
+pub fn registered(token: Result<PlayerToken, PlayerAuthError>, game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>) -> (ContentType, String) {
+    let token = match token {
+        Ok(token) => token,
+        Err(_err) => return (ContentType::Text, String::from("false")),
+    };
+    let mut game_manager = metrics.write(game_manager);
+    match game_manager.game_by_player_id(token.player_id) {
+        Some(game) if game.game_id() == token.game_id => {
+            match game.completed() {
+                Some(win) => {
+                    if win {
+                        (ContentType::Text, String::from("won"))
+                    } else {
+                        (ContentType::Text, String::from("lost"))
+                    }
+                },
+                None => (ContentType::Text, String::from("playing"))
+            }
+        },
+        _ => (ContentType::Text, String::from("false")),
+    }
+}
+
+/// Metrics in the prometheus text format
+/// 
+/// See [Metrics](../metrics/struct.Metrics.html) for the available metrics.
+#[openapi(tag = "Health")]
+#[get("/metrics")]
+pub fn metrics(game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>) -> (ContentType, String) {
+    let game_manager = metrics.read(game_manager);
+    let out = metrics.render(game_manager.games().len(), MAX_ACTIVE_GAMES, game_manager.results());
+    (ContentType::new("text", "plain").with_params(("version", "0.0.4")), out)
+}
+
+/// Some utility functions
+mod utils {
+    use std::{net::IpAddr, sync::RwLockWriteGuard};
+
+    use rocket::http::Status;
+
+    use crate::{game::{GameManager, RegisterError, RegisterResult, base_game::Game, series::RematchError}, metrics::Metrics, rate_limit::RateLimiter, request_data::{PlayerAuth, PlayerAuthError, PlayerToken, SeriesView}, session::SessionConfig};
+
+    /// Registers a new game for the client and issues the token of the new player.
+    /// 
+    /// The player that sent `old_token` leaves the previous game, so that the old token can not be used anymore.
+    /// # Returns
+    /// The result of the registration and the token or why the game is rejected
+    pub fn register_player(game_manager: &mut GameManager, metrics: &Metrics, session_config: &SessionConfig, rate_limiter: &RateLimiter, client: Option<IpAddr>, old_token: Result<PlayerToken, PlayerAuthError>) -> Result<(RegisterResult, String), RegisterError> {
+        delete_old_game(game_manager, old_token);
+        let result = game_manager.register_game_for(client, rate_limiter.max_games_per_client)?;
+        let token = issue_token(metrics, session_config, &result);
+        Ok((result, token))
+    }
+
+    /// Removes the player that sent `old_token` from the previous game, so that the old token can not be used anymore.
+    /// 
+    /// The other players of the game keep playing, the game is only deleted when nobody else is left in it.
+    pub fn delete_old_game(game_manager: &mut GameManager, old_token: Result<PlayerToken, PlayerAuthError>) {
+        if let Ok(old_token) = old_token {
+            game_manager.leave_game(old_token.player_id);
+        }
+    }
+
+    /// Records the registration and issues the token of the new player
+    pub fn issue_token(metrics: &Metrics, session_config: &SessionConfig, result: &RegisterResult) -> String {
+        metrics.record_registration(result.evicted);
+        session_config.issue(result.player_id, result.game_id)
+    }
+
+    /// Returns the game a player is assigned to by using the `player_auth`
+    pub fn game_by_player_auth<'a>(game_manager: &'a mut RwLockWriteGuard<GameManager>, player_auth: PlayerAuth) -> Option<&'a mut Game> {
+        match game_manager.game_by_player_id(player_auth.player_id) {
+            Some(game) => Some(game),
+            None => None,
+        }
+    }
+
+    /// Collects the results of the rounds of the game
+    pub fn series_view(game: &Game) -> SeriesView {
+        let series = game.series();
+        SeriesView {
+            best_of: series.best_of(),
+            rounds: series.rounds(),
+            won: series.solved(),
+            lost: series.rounds() - series.solved(),
+            wins: game.turns().map(|turns| turns.players().iter().map(|player| series.wins(*player)).collect()).unwrap_or_default(),
+            finished: series.finished(),
+        }
+    }
+
+    /// The status with which a rematch that is not possible is rejected
+    pub fn rematch_status(err: &RematchError) -> Status {
+        match err {
+            RematchError::NotFound => Status::NotFound,
+            RematchError::InvalidLength => Status::BadRequest,
+            RematchError::RoundRunning | RematchError::TurnBased | RematchError::Race => Status::Conflict,
+        }
+    }
+}
@@ -0,0 +1,639 @@
+use std::{net::IpAddr, sync::{Arc, RwLock}, time::{Duration, Instant, UNIX_EPOCH}};
+
+use rocket::{http::{CookieJar, Cookie, Status}, response::status::{Created, Custom}, serde::json::Json, Shutdown, State};
+use rocket_okapi::openapi;
+use uuid::Uuid;
+
+use crate::{events::{Events, GameEvent, GameEventStream}, game::{base_game::Game, bot::BotError, chat::ChatMessage, guess::{outcome_name, Guess}, race::{RaceError, RaceState}, solver::Solver, timer::{Timer, DEFAULT_TIME_ATTACK_DURATION, MAX_TIME_LIMIT}, turns::{TurnError, DEFAULT_TURN_TIMEOUT}, GameManager, RegisterError, MAX_LIVES}, metrics::Metrics, rate_limit::{ChatMessages, Guesses, RateLimit, RateLimiter, Registrations, WithRetryAfter, GAME_LIMIT_RETRY_AFTER}, request_data::{ApiError, BotRequest, ChatMessageView, ChatRequest, CreatedGame, GameRequest, GameView, GuessRequest, GuessResponse, HintView, JoinedRace, LetterHintView, PlayerAuth, PlayerAuthError, PlayerToken, PlayerView, RaceView, RacerView, RematchRequest, SpectatingRequest, SpectatorAuth, SpectatorLink, SpectatorView, TimeAttackRequest, TimeView, TurnGameRequest, TurnView, WordRequest, TOKEN_COOKIE}, session::SessionConfig};
+
+use super::utils::{delete_old_game, issue_token, register_player, rematch_status, series_view};
+
+/// Error response of the v2 api, rejected registrations also contain the `Retry-After` header
+type ApiFailure = WithRetryAfter<Custom<Json<ApiError>>>;
+
+/// Result of the v2 api
+type ApiResult<T> = Result<T, ApiFailure>;
+
+/// Creates a new game and registers the player to it
+///
+/// Works like [register](../fn.register.html), the token is returned together with the state of the new game. The
+/// body can contain time limits for each guess and for the whole game.
+///
+/// # Return
+/// `201` with the location of the game
+///
+/// `400` when a time limit is longer than an hour
+///
+/// `429` when the client already has the maximum number of games or the server is full
+#[openapi(tag = "Game v2")]
+#[post("/api/v2/games", data = "<settings>")]
+#[allow(clippy::too_many_arguments)]
+pub fn create_game(_limit: RateLimit<Registrations>, client: Option<IpAddr>, cookies: &CookieJar<'_>, old_token: Result<PlayerToken, PlayerAuthError>, game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, session_config: &State<SessionConfig>, rate_limiter: &State<RateLimiter>, settings: Option<Json<GameRequest>>) -> ApiResult<Created<Json<CreatedGame>>> {
+    let guess_limit = time_limit(settings.as_ref().and_then(|settings| settings.guess_time_limit))?;
+    let game_limit = time_limit(settings.as_ref().and_then(|settings| settings.game_time_limit))?;
+    let mut game_manager = metrics.write(game_manager);
+    let (result, token) = match (guess_limit, game_limit) {
+        (None, None) => register_player(&mut game_manager, metrics, session_config, rate_limiter, client, old_token).map_err(too_many_games)?,
+        _ => {
+            delete_old_game(&mut game_manager, old_token);
+            let result = game_manager.register_timed_game(client, rate_limiter.max_games_per_client, Timer::new(guess_limit, game_limit)).map_err(too_many_games)?;
+            let token = issue_token(metrics, session_config, &result);
+            (result, token)
+        },
+    };
+    cookies.add(session_config.cookie(token.clone()));
+    let game = game_view(game_manager.game_by_id(result.game_id).unwrap(), result.player_id);
+    Ok(Created::new(format!("/api/v2/games/{}", result.game_id)).body(Json(CreatedGame { token, game })))
+}
+
+/// Creates a new evil game in which the word is not chosen upfront
+///
+/// The server keeps all words of the words file that match the guesses so far and reveals a letter only when most
+/// of these words contain it, see [Game::evil](../../game/base_game/struct.Game.html#method.evil).
+///
+/// # Return
+/// `201` with the location of the game
+///
+/// `429` when the client already has the maximum number of games or the server is full
+#[openapi(tag = "Game v2")]
+#[post("/api/v2/evil_games")]
+#[allow(clippy::too_many_arguments)]
+pub fn create_evil_game(_limit: RateLimit<Registrations>, client: Option<IpAddr>, cookies: &CookieJar<'_>, old_token: Result<PlayerToken, PlayerAuthError>, game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, session_config: &State<SessionConfig>, rate_limiter: &State<RateLimiter>) -> ApiResult<Created<Json<CreatedGame>>> {
+    let mut game_manager = metrics.write(game_manager);
+    delete_old_game(&mut game_manager, old_token);
+    let result = game_manager.register_evil_game(client, rate_limiter.max_games_per_client).map_err(too_many_games)?;
+    let token = issue_token(metrics, session_config, &result);
+    cookies.add(session_config.cookie(token.clone()));
+    let game = game_view(game_manager.game_by_id(result.game_id).unwrap(), result.player_id);
+    Ok(Created::new(format!("/api/v2/games/{}", result.game_id)).body(Json(CreatedGame { token, game })))
+}
+
+/// Creates a new time-attack game in which the player solves as many words as possible within the duration
+///
+/// A new word is started as soon as the previous word has been solved or lost, the number of solved words is the
+/// score of the game.
+///
+/// # Return
+/// `201` with the location of the game
+///
+/// `400` when the duration is `0` or longer than an hour
+///
+/// `429` when the client already has the maximum number of games or the server is full
+#[openapi(tag = "Time attack")]
+#[post("/api/v2/time_attack", data = "<settings>")]
+#[allow(clippy::too_many_arguments)]
+pub fn create_time_attack(_limit: RateLimit<Registrations>, client: Option<IpAddr>, cookies: &CookieJar<'_>, old_token: Result<PlayerToken, PlayerAuthError>, game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, session_config: &State<SessionConfig>, rate_limiter: &State<RateLimiter>, settings: Option<Json<TimeAttackRequest>>) -> ApiResult<Created<Json<CreatedGame>>> {
+    let duration = match settings.and_then(|settings| settings.duration) {
+        Some(seconds) => time_limit(Some(seconds))?.ok_or_else(|| api_error(Status::BadRequest, "The duration has to be at least one second"))?,
+        None => DEFAULT_TIME_ATTACK_DURATION,
+    };
+    let mut game_manager = metrics.write(game_manager);
+    delete_old_game(&mut game_manager, old_token);
+    let result = game_manager.register_time_attack(client, rate_limiter.max_games_per_client, duration).map_err(too_many_games)?;
+    let token = issue_token(metrics, session_config, &result);
+    cookies.add(session_config.cookie(token.clone()));
+    let game = game_view(game_manager.game_by_id(result.game_id).unwrap(), result.player_id);
+    Ok(Created::new(format!("/api/v2/games/{}", result.game_id)).body(Json(CreatedGame { token, game })))
+}
+
+/// The state of the game
+///
+/// # Return
+/// `403` when the player is not assigned to the game
+#[openapi(tag = "Game v2")]
+#[get("/api/v2/games/<id>")]
+pub fn game(game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, player_auth: PlayerAuth, id: Uuid) -> ApiResult<Json<GameView>> {
+    let mut game_manager = metrics.write(game_manager);
+    let game = own_game(&mut game_manager, player_auth, id)?;
+    Ok(Json(game_view(game, player_auth.player_id)))
+}
+
+/// Submits a letter or word to the game
+///
+/// # Return
+/// The result of [Game::guess](../../game/base_game/struct.Game.html#method.guess) together with the new state of the game
+///
+/// `400` when the guess is invalid, no life is lost in that case
+///
+/// `403` when the player is not assigned to the game
+///
+/// `409` when it is not the turn of the player in a turn-based game
+#[openapi(tag = "Game v2")]
+#[post("/api/v2/games/<id>/guesses", data = "<guess>")]
+pub fn submit_guess(_limit: RateLimit<Guesses>, game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, player_auth: PlayerAuth, id: Uuid, guess: Json<GuessRequest>) -> ApiResult<Json<GuessResponse>> {
+    let guess: Guess = guess.guess.parse().map_err(|err| api_error(Status::BadRequest, err))?;
+    let mut game_manager = metrics.write(game_manager);
+    own_game(&mut game_manager, player_auth, id)?;
+    let result = game_manager.guess(id, player_auth.player_id, guess).map_err(turn_error)?;
+    let game = game_manager.game_by_id(id).unwrap();
+    metrics.record_guess(result);
+    Ok(Json(GuessResponse {
+        result,
+        outcome: String::from(outcome_name(result)),
+        game: game_view(game, player_auth.player_id),
+    }))
+}
+
+/// Starts a new round with a new word for the players of the game once it has ended
+///
+/// Works like [rematch](../fn.rematch.html), the game id and the tokens stay the same.
+///
+/// # Return
+/// The state of the game with the new word
+///
+/// `400` when the length of the series is invalid
+///
+/// `403` when the player is not assigned to the game
+///
+/// `409` when the game has not ended yet, is turn-based or part of a race
+#[openapi(tag = "Game v2")]
+#[post("/api/v2/games/<id>/rematch", data = "<rematch>")]
+pub fn rematch(game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, player_auth: PlayerAuth, id: Uuid, rematch: Option<Json<RematchRequest>>) -> ApiResult<Json<GameView>> {
+    let mut game_manager = metrics.write(game_manager);
+    own_game(&mut game_manager, player_auth, id)?;
+    game_manager.rematch(id, rematch.and_then(|rematch| rematch.best_of)).map_err(|err| api_error(rematch_status(&err), err))?;
+    Ok(Json(game_view(game_manager.game_by_id(id).unwrap(), player_auth.player_id)))
+}
+
+/// Suggests the next guess for the game
+///
+/// The solver keeps the words of the words file that match the revealed letters and the wrong guesses and ranks the
+/// letters that have not been guessed yet by the information they reveal about the word, see
+/// [Solver](../../game/solver/struct.Solver.html). The five best letters are returned.
+///
+/// # Return
+/// `403` when the player is not assigned to the game
+///
+/// `404` when the game does not exist
+///
+/// `409` when the game has ended or is part of a race
+#[openapi(tag = "Solver")]
+#[get("/api/v2/games/<id>/hint")]
+pub fn hint(game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, player_auth: PlayerAuth, id: Uuid) -> ApiResult<Json<HintView>> {
+    let mut game_manager = metrics.write(game_manager);
+    own_game(&mut game_manager, player_auth, id)?;
+    if game_manager.race_of_game(id).is_some() {
+        return Err(api_error(Status::Conflict, "Hints are not available in races"));
+    }
+    let words = game_manager.words().list();
+    let game = game_manager.game_by_id(id).unwrap();
+    if game.status() != "playing" {
+        return Err(api_error(Status::Conflict, "The game is not running"));
+    }
+    let guessed: Vec<char> = game.guessed_letters().chars().filter(|letter| letter.is_alphabetic()).collect();
+    let solver = Solver::new(words.words(), &game.game_string(), &guessed);
+    Ok(Json(HintView {
+        guess: solver.suggest().map(|guess| guess.to_string()),
+        candidates: solver.candidates().len(),
+        letters: solver.rank().into_iter().take(5).map(|score| LetterHintView {
+            letter: score.letter,
+            information: score.information,
+            probability: score.probability,
+        }).collect(),
+    }))
+}
+
+/// Deletes the game and removes the session cookie
+///
+/// # Return
+/// `204` when the game was deleted
+///
+/// `403` when the player is not assigned to the game
+#[openapi(tag = "Game v2")]
+#[delete("/api/v2/games/<id>")]
+pub fn delete_game(cookies: &CookieJar<'_>, game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, player_auth: PlayerAuth, id: Uuid) -> ApiResult<Status> {
+    let mut game_manager = metrics.write(game_manager);
+    own_game(&mut game_manager, player_auth, id)?;
+    game_manager.delete_game_by_id(id);
+    cookies.remove(Cookie::named(TOKEN_COOKIE));
+    Ok(Status::NoContent)
+}
+
+/// The players of the game
+///
+/// # Return
+/// `403` when the player is not assigned to the game
+#[openapi(tag = "Game v2")]
+#[get("/api/v2/games/<id>/players")]
+pub fn players(game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, player_auth: PlayerAuth, id: Uuid) -> ApiResult<Json<Vec<PlayerView>>> {
+    let mut game_manager = metrics.write(game_manager);
+    let game = own_game(&mut game_manager, player_auth, id)?;
+    Ok(Json(game.players().into_iter().map(|player| PlayerView {
+        name: player.name.clone(),
+        you: player.id == player_auth.player_id,
+    }).collect()))
+}
+
+/// Creates a new race with a random word and registers the player to it
+///
+/// Other players can join the race with the returned `race_id`. Each player guesses in an own game, the guesses are
+/// submitted to `/api/v2/games/<game_id>/guesses`. The first player that solves the word wins and the games of the
+/// other players are ended.
+///
+/// # Return
+/// `201` with the location of the race
+///
+/// `429` when the client already has the maximum number of games or the server is full
+#[openapi(tag = "Race")]
+#[post("/api/v2/races")]
+#[allow(clippy::too_many_arguments)]
+pub fn create_race(_limit: RateLimit<Registrations>, client: Option<IpAddr>, cookies: &CookieJar<'_>, old_token: Result<PlayerToken, PlayerAuthError>, game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, session_config: &State<SessionConfig>, rate_limiter: &State<RateLimiter>) -> ApiResult<Created<Json<JoinedRace>>> {
+    let mut game_manager = metrics.write(game_manager);
+    delete_old_game(&mut game_manager, old_token);
+    let (race_id, result) = game_manager.create_race(client, rate_limiter.max_games_per_client).map_err(too_many_games)?;
+    let token = issue_token(metrics, session_config, &result);
+    cookies.add(session_config.cookie(token.clone()));
+    let game = game_view(game_manager.game_by_id(result.game_id).unwrap(), result.player_id);
+    Ok(Created::new(format!("/api/v2/races/{}", race_id)).body(Json(JoinedRace { token, race_id, game })))
+}
+
+/// Registers the player to the race, the player gets an own game with the word of the race
+///
+/// When the request contains a valid token that player leaves the previous game, the game is deleted when nobody else
+/// is left in it.
+///
+/// # Return
+/// `201` with the location of the race
+///
+/// `404` when the race does not exist
+///
+/// `409` when the race has already finished
+///
+/// `429` when the client already has the maximum number of games or the server is full
+#[openapi(tag = "Race")]
+#[post("/api/v2/races/<id>/players")]
+#[allow(clippy::too_many_arguments)]
+pub fn join_race(_limit: RateLimit<Registrations>, client: Option<IpAddr>, cookies: &CookieJar<'_>, old_token: Result<PlayerToken, PlayerAuthError>, game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, session_config: &State<SessionConfig>, rate_limiter: &State<RateLimiter>, id: Uuid) -> ApiResult<Created<Json<JoinedRace>>> {
+    let mut game_manager = metrics.write(game_manager);
+    delete_old_game(&mut game_manager, old_token);
+    let result = game_manager.join_race(id, client, rate_limiter.max_games_per_client).map_err(|err| match err {
+        RaceError::NotFound => api_error(Status::NotFound, err),
+        RaceError::Finished => api_error(Status::Conflict, err),
+        RaceError::Register(err) => too_many_games(err),
+    })?;
+    let token = issue_token(metrics, session_config, &result);
+    cookies.add(session_config.cookie(token.clone()));
+    let game = game_view(game_manager.game_by_id(result.game_id).unwrap(), result.player_id);
+    Ok(Created::new(format!("/api/v2/races/{}", id)).body(Json(JoinedRace { token, race_id: id, game })))
+}
+
+/// The progress of all players in the race and the result when the race has finished
+///
+/// Only the number of revealed letters and the lives of the other players are visible, not their letters.
+///
+/// # Return
+/// `403` when the player is not part of the race
+#[openapi(tag = "Race")]
+#[get("/api/v2/races/<id>")]
+pub fn race(game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, player_auth: PlayerAuth, id: Uuid) -> ApiResult<Json<RaceView>> {
+    match metrics.read(game_manager).race_of_game(player_auth.game_id) {
+        Some(race) if race.id == id => Ok(Json(race_view(race, player_auth))),
+        _ => Err(api_error(Status::Forbidden, "You are not part of this race")),
+    }
+}
+
+/// Adds a bot to the race that plays its own game with the word of the race
+///
+/// The bot guesses every two seconds, its strength is the chance out of 10 that it chooses the best guess of the
+/// [Solver](../../game/solver/struct.Solver.html) instead of a random letter. The race is deleted once only bots are
+/// left in it.
+///
+/// # Return
+/// `201` with the state of the race
+///
+/// `400` when the strength is not between 1 and 10
+///
+/// `403` when the player is not part of the race
+///
+/// `409` when the race has finished or already has four bots
+///
+/// `429` when the client already has the maximum number of games or the server is full, the game of the bot counts against the client
+#[openapi(tag = "Solver")]
+#[post("/api/v2/races/<id>/bots", data = "<settings>")]
+pub fn add_race_bot(client: Option<IpAddr>, game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, rate_limiter: &State<RateLimiter>, player_auth: PlayerAuth, id: Uuid, settings: Option<Json<BotRequest>>) -> ApiResult<Created<Json<RaceView>>> {
+    let mut game_manager = metrics.write(game_manager);
+    if game_manager.race_of_game(player_auth.game_id).map(|race| race.id) != Some(id) {
+        return Err(api_error(Status::Forbidden, "You are not part of this race"));
+    }
+    game_manager.add_race_bot(id, bot_strength(settings), client, rate_limiter.max_games_per_client).map_err(bot_error)?;
+    let race = game_manager.race_of_game(player_auth.game_id).unwrap();
+    Ok(Created::new(format!("/api/v2/races/{}", id)).body(Json(race_view(race, player_auth))))
+}
+
+/// Creates a new turn-based game in which the player chooses the first word
+///
+/// Other players join with `POST /api/v2/games/<id>/players`. In each round one player chooses the word and the
+/// others guess one after another, a player that does not guess within the timeout is skipped. The role of the
+/// chooser is passed on to the next player in each round.
+///
+/// # Return
+/// `201` with the location of the game
+///
+/// `400` when the timeout is longer than an hour
+///
+/// `429` when the client already has the maximum number of games or the server is full
+#[openapi(tag = "Turn-based")]
+#[post("/api/v2/turn_games", data = "<settings>")]
+#[allow(clippy::too_many_arguments)]
+pub fn create_turn_game(_limit: RateLimit<Registrations>, client: Option<IpAddr>, cookies: &CookieJar<'_>, old_token: Result<PlayerToken, PlayerAuthError>, game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, session_config: &State<SessionConfig>, rate_limiter: &State<RateLimiter>, settings: Option<Json<TurnGameRequest>>) -> ApiResult<Created<Json<CreatedGame>>> {
+    let timeout = match settings.as_ref().and_then(|settings| settings.turn_timeout) {
+        Some(seconds) => time_limit(Some(seconds))?.unwrap_or(Duration::ZERO),
+        None => DEFAULT_TURN_TIMEOUT,
+    };
+    let dictionary = settings.map(|settings| settings.dictionary).unwrap_or(false);
+    let mut game_manager = metrics.write(game_manager);
+    delete_old_game(&mut game_manager, old_token);
+    let result = game_manager.register_turn_game(client, rate_limiter.max_games_per_client, timeout, dictionary).map_err(too_many_games)?;
+    let token = issue_token(metrics, session_config, &result);
+    cookies.add(session_config.cookie(token.clone()));
+    let game = game_view(game_manager.game_by_id(result.game_id).unwrap(), result.player_id);
+    Ok(Created::new(format!("/api/v2/games/{}", result.game_id)).body(Json(CreatedGame { token, game })))
+}
+
+/// Registers the player to the turn-based game, players can only join between rounds
+///
+/// When the request contains a valid token that player leaves the previous game, the game is deleted when nobody else
+/// is left in it.
+///
+/// # Return
+/// `201` with the location of the game
+///
+/// `404` when the game does not exist
+///
+/// `409` when the game is not turn-based or a round is running
+#[openapi(tag = "Turn-based")]
+#[post("/api/v2/games/<id>/players")]
+#[allow(clippy::too_many_arguments)]
+pub fn join_game(_limit: RateLimit<Registrations>, cookies: &CookieJar<'_>, old_token: Result<PlayerToken, PlayerAuthError>, game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, session_config: &State<SessionConfig>, id: Uuid) -> ApiResult<Created<Json<CreatedGame>>> {
+    let mut game_manager = metrics.write(game_manager);
+    let result = game_manager.join_game(id).map_err(turn_error)?;
+    if old_token.as_ref().map(|token| token.game_id != id).unwrap_or(false) {
+        delete_old_game(&mut game_manager, old_token);
+    }
+    let token = issue_token(metrics, session_config, &result);
+    cookies.add(session_config.cookie(token.clone()));
+    let game = game_view(game_manager.game_by_id(result.game_id).unwrap(), result.player_id);
+    Ok(Created::new(format!("/api/v2/games/{}", id)).body(Json(CreatedGame { token, game })))
+}
+
+/// Starts the next round of the turn-based game with the word
+///
+/// The host chooses the first word, afterwards the next player in the order chooses. The word is normalized like a
+/// guess and may only contain letters and `-`.
+///
+/// # Return
+/// The state of the game
+///
+/// `400` when the word is invalid or not contained in the words file when the game requires it
+///
+/// `409` when another player chooses the word, the round is still running or nobody has joined yet
+#[openapi(tag = "Turn-based")]
+#[put("/api/v2/games/<id>/word", data = "<word>")]
+pub fn choose_word(game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, player_auth: PlayerAuth, id: Uuid, word: Json<WordRequest>) -> ApiResult<Json<GameView>> {
+    let mut game_manager = metrics.write(game_manager);
+    own_game(&mut game_manager, player_auth, id)?;
+    game_manager.choose_word(id, player_auth.player_id, &word.word).map_err(turn_error)?;
+    Ok(Json(game_view(game_manager.game_by_id(id).unwrap(), player_auth.player_id)))
+}
+
+/// Adds a bot to the turn-based game, bots can only join between rounds
+///
+/// The bot guesses when it is its turn and chooses a random word when it is the chooser. Like in races the strength
+/// is the chance out of 10 that it chooses the best guess.
+///
+/// # Return
+/// `201` with the state of the game
+///
+/// `400` when the strength is not between 1 and 10
+///
+/// `403` when the player is not assigned to the game
+///
+/// `409` when the game is not turn-based, a round is running or the game already has four bots
+#[openapi(tag = "Solver")]
+#[post("/api/v2/games/<id>/bots", data = "<settings>")]
+pub fn add_game_bot(game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, player_auth: PlayerAuth, id: Uuid, settings: Option<Json<BotRequest>>) -> ApiResult<Created<Json<GameView>>> {
+    let mut game_manager = metrics.write(game_manager);
+    own_game(&mut game_manager, player_auth, id)?;
+    game_manager.add_game_bot(id, bot_strength(settings)).map_err(bot_error)?;
+    let game = game_view(game_manager.game_by_id(id).unwrap(), player_auth.player_id);
+    Ok(Created::new(format!("/api/v2/games/{}", id)).body(Json(game)))
+}
+
+/// Creates a link with which spectators can watch the game without being able to guess
+///
+/// Every call creates a new spectator token, the links stay valid until they expire, the game is deleted or the host
+/// disables spectating.
+///
+/// # Return
+/// `201` with the spectator token and the path under which the game can be watched
+///
+/// `403` when the player is not assigned to the game
+///
+/// `409` when the host has disabled spectating
+#[openapi(tag = "Spectators")]
+#[post("/api/v2/games/<id>/spectators")]
+pub fn create_spectator_link(game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, session_config: &State<SessionConfig>, player_auth: PlayerAuth, id: Uuid) -> ApiResult<Created<Json<SpectatorLink>>> {
+    let mut game_manager = metrics.write(game_manager);
+    if !own_game(&mut game_manager, player_auth, id)?.spectating() {
+        return Err(api_error(Status::Conflict, "The host has disabled spectating"));
+    }
+    let token = session_config.issue_spectator(Uuid::new_v4(), id);
+    let url = format!("/api/v2/spectate?token={}", token);
+    Ok(Created::new(url.clone()).body(Json(SpectatorLink { token, url })))
+}
+
+/// The state of the game that is watched with the spectator token in the `token` query parameter
+///
+/// Spectators see the same as the players, the requests are used to count the spectators.
+///
+/// # Return
+/// `403` when the token is invalid, the game does not exist anymore or spectating has been disabled
+#[openapi(tag = "Spectators")]
+#[get("/api/v2/spectate")]
+pub fn spectate(game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, spectator_auth: SpectatorAuth) -> ApiResult<Json<SpectatorView>> {
+    let mut game_manager = metrics.write(game_manager);
+    let game = game_manager.game_by_id(spectator_auth.game_id).ok_or_else(|| api_error(Status::Forbidden, "The game does not exist"))?;
+    game.watch(spectator_auth.spectator_id);
+    Ok(Json(SpectatorView {
+        guesses: game.history().to_vec(),
+        game: game_view(game, spectator_auth.spectator_id),
+    }))
+}
+
+/// Allows or forbids spectators to watch the game, only the host of the game can change this
+///
+/// # Return
+/// The state of the game
+///
+/// `403` when the player is not the host of the game
+#[openapi(tag = "Spectators")]
+#[put("/api/v2/games/<id>/spectating", data = "<spectating>")]
+pub fn set_spectating(game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, player_auth: PlayerAuth, id: Uuid, spectating: Json<SpectatingRequest>) -> ApiResult<Json<GameView>> {
+    let mut game_manager = metrics.write(game_manager);
+    let game = own_game(&mut game_manager, player_auth, id)?;
+    if game.host() != player_auth.player_id {
+        return Err(api_error(Status::Forbidden, "Only the host can change who can watch the game"));
+    }
+    game.set_spectating(spectating.enabled);
+    Ok(Json(game_view(game, player_auth.player_id)))
+}
+
+/// The chat messages of the game, the oldest message comes first
+///
+/// # Return
+/// `403` when the player is not assigned to the game
+#[openapi(tag = "Chat")]
+#[get("/api/v2/games/<id>/chat")]
+pub fn chat(game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, player_auth: PlayerAuth, id: Uuid) -> ApiResult<Json<Vec<ChatMessageView>>> {
+    let mut game_manager = metrics.write(game_manager);
+    let game = own_game(&mut game_manager, player_auth, id)?;
+    Ok(Json(game.chat().iter().map(|message| chat_message_view(game, message)).collect()))
+}
+
+/// Sends a chat message to the other players, the message is delivered over the event stream
+///
+/// # Return
+/// `201` with the message
+///
+/// `400` when the message is empty, too long or reveals the word while the round is running
+///
+/// `403` when the player is not assigned to the game
+#[openapi(tag = "Chat")]
+#[post("/api/v2/games/<id>/chat", data = "<message>")]
+pub fn send_message(_limit: RateLimit<ChatMessages>, game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, events: &State<Events>, player_auth: PlayerAuth, id: Uuid, message: Json<ChatRequest>) -> ApiResult<Created<Json<ChatMessageView>>> {
+    let mut game_manager = metrics.write(game_manager);
+    let game = own_game(&mut game_manager, player_auth, id)?;
+    let message = game.post_message(player_auth.player_id, &message.text).map_err(|err| api_error(Status::BadRequest, err))?;
+    let message = chat_message_view(game, &message);
+    events.publish(id, GameEvent::Chat(message.clone()));
+    Ok(Created::new(format!("/api/v2/games/{}/chat", id)).body(Json(message)))
+}
+
+/// Live events of the game as server-sent events, each event contains a json object with the `type` of the event
+///
+/// # Return
+/// `403` when the player is not assigned to the game
+#[openapi(tag = "Chat")]
+#[get("/api/v2/games/<id>/events")]
+pub fn events(game_manager: &State<Arc<RwLock<GameManager>>>, metrics: &State<Metrics>, events: &State<Events>, player_auth: PlayerAuth, id: Uuid, shutdown: Shutdown) -> ApiResult<GameEventStream> {
+    own_game(&mut metrics.write(game_manager), player_auth, id)?;
+    Ok(events.stream(id, shutdown))
+}
+
+/// Converts a time limit in seconds, `0` disables the limit
+fn time_limit(seconds: Option<u64>) -> ApiResult<Option<Duration>> {
+    let limit = seconds.map(Duration::from_secs).filter(|limit| !limit.is_zero());
+    if limit.map(|limit| limit > MAX_TIME_LIMIT).unwrap_or(false) {
+        return Err(api_error(Status::BadRequest, format!("Time limits can not be longer than {} seconds", MAX_TIME_LIMIT.as_secs())));
+    }
+    Ok(limit)
+}
+
+/// Returns the game with the id when the player is assigned to it
+fn own_game(game_manager: &mut GameManager, player_auth: PlayerAuth, id: Uuid) -> ApiResult<&mut Game> {
+    if player_auth.game_id != id {
+        return Err(api_error(Status::Forbidden, "You are not assigned to this game"));
+    }
+    game_manager.game_by_id(id).ok_or_else(|| api_error(Status::NotFound, "The game does not exist"))
+}
+
+/// Collects the state of the game that is visible to the player
+fn game_view(game: &Game, player_id: Uuid) -> GameView {
+    GameView {
+        game_id: game.game_id(),
+        game_string: game.game_string(),
+        lives: game.lives(),
+        max_lives: MAX_LIVES,
+        guessed_letters: game.guessed_letters().split_whitespace().map(String::from).collect(),
+        status: String::from(game.status()),
+        word: game.word(),
+        turn: game.turns().map(|turns| {
+            let running = game.status() == "playing";
+            let now = Instant::now();
+            TurnView {
+                round: turns.round(),
+                you: game.player_number(player_id),
+                players: turns.players().len(),
+                chooser: game.player_number(if game.completed().is_some() { turns.next_chooser() } else { turns.chooser() }),
+                current: running.then(|| game.player_number(turns.current_at(now))),
+                seconds_left: turns.time_left(now).filter(|_| running).map(|left| left.as_secs()),
+                dictionary: turns.dictionary(),
+            }
+        }),
+        spectators: game.spectators(),
+        spectating: game.spectating(),
+        series: series_view(game),
+        time: game.timer().map(|timer| TimeView {
+            guess_seconds_left: timer.guess_time_left(Instant::now()).map(|left| left.as_secs()),
+            game_seconds_left: timer.game_time_left(Instant::now()).map(|left| left.as_secs()),
+            score: game.is_time_attack().then(|| game.series().solved()),
+        }),
+        evil: game.is_evil(),
+    }
+}
+
+/// Converts the state of the race into the view of the player
+fn race_view(race: RaceState, player_auth: PlayerAuth) -> RaceView {
+    let tie = race.tie();
+    let player = |game_id| race.racers.iter().position(|racer| racer.game_id == game_id).map(|index| index + 1);
+    let winners = race.winners.iter().flatten().filter_map(|game_id| player(*game_id)).collect();
+    RaceView {
+        race_id: race.id,
+        status: String::from(if race.winners.is_some() { "finished" } else { "running" }),
+        word: race.word,
+        players: race.racers.iter().enumerate().map(|(index, racer)| RacerView {
+            player: index + 1,
+            you: racer.game_id == player_auth.game_id,
+            revealed: racer.revealed,
+            letters: racer.letters,
+            lives: racer.lives,
+            status: String::from(racer.status),
+        }).collect(),
+        winners,
+        tie,
+    }
+}
+
+/// Converts the chat message into the view that is sent to the players
+fn chat_message_view(game: &Game, message: &ChatMessage) -> ChatMessageView {
+    ChatMessageView {
+        player: game.player_number(message.player_id),
+        text: message.text.clone(),
+        sent: message.sent.duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or_default(),
+    }
+}
+
+/// Converts the error of a turn-based game into the response
+fn turn_error(err: TurnError) -> ApiFailure {
+    let status = match err {
+        TurnError::NotFound => Status::NotFound,
+        TurnError::InvalidWord(_) | TurnError::NotInDictionary => Status::BadRequest,
+        _ => Status::Conflict,
+    };
+    api_error(status, err)
+}
+
+/// Returns the requested strength of a bot, bots have the strength `5` by default
+fn bot_strength(settings: Option<Json<BotRequest>>) -> u8 {
+    settings.and_then(|settings| settings.strength).unwrap_or(5)
+}
+
+/// Converts the reason why a bot can not join into the response
+fn bot_error(err: BotError) -> ApiFailure {
+    match err {
+        BotError::InvalidStrength => api_error(Status::BadRequest, err),
+        BotError::Turn(err) => turn_error(err),
+        BotError::Race(RaceError::NotFound) => api_error(Status::NotFound, err),
+        BotError::Race(RaceError::Register(err)) => too_many_games(err),
+        _ => api_error(Status::Conflict, err),
+    }
+}
+
+/// Converts the rejection of a registration because of the game limits into the response
+fn too_many_games(err: RegisterError) -> ApiFailure {
+    WithRetryAfter::new(Custom(Status::TooManyRequests, Json(ApiError { error: err.to_string() })), GAME_LIMIT_RETRY_AFTER)
+}
+
+fn api_error(status: Status, error: impl ToString) -> ApiFailure {
+    Custom(status, Json(ApiError { error: error.to_string() })).into()
+}
@@ -0,0 +1,297 @@
+use std::{collections::HashMap, marker::PhantomData, sync::Mutex, time::{Duration, Instant}};
+
+use rocket::{http::{ContentType, Header, Status}, request::{FromRequest, Outcome}, response::{self, Responder}, Request};
+use rocket_okapi::{gen::OpenApiGenerator, okapi::openapi3::{RefOr, Response, Responses}, request::{OpenApiFromRequest, RequestHeaderInput}, response::OpenApiResponderInner};
+use serde::Deserialize;
+
+use crate::request_data::PlayerToken;
+
+/// Maximum number of buckets, when it is reached the buckets that are full again are removed and then the least recently
+/// used buckets until there is room for a new one
+const MAX_BUCKETS: usize = 10_000;
+
+/// Time after which a client should try again when its registration was rejected because of the game limits
+pub const GAME_LIMIT_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Limit of a route, `capacity` requests are allowed at once and the requests are refilled over `seconds`.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct Limit {
+    pub capacity: u32,
+    pub seconds: u64,
+}
+
+impl Limit {
+    /// Time after which one request is available again
+    fn refill_interval(&self) -> Duration {
+        Duration::from_secs(self.seconds) / self.capacity.max(1)
+    }
+}
+
+/// Remaining requests of a client on a route
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+    limit: Limit,
+}
+
+impl Bucket {
+    /// Checks whether all requests of the bucket are available again
+    fn full(&self, now: Instant) -> bool {
+        now.duration_since(self.updated) >= self.limit.refill_interval() * self.limit.capacity
+    }
+}
+
+/// Limits the number of requests per client with token buckets.
+///
+/// Each [LimitedRoute] has its own limit that can be configured in the `rate_limits` table of `Rocket.toml`, for example
+/// `rate_limits = { register = { capacity = 10, seconds = 60 } }`.
+pub struct RateLimiter {
+    limits: HashMap<String, Limit>,
+    buckets: Mutex<HashMap<(&'static str, String), Bucket>>,
+    /// Maximum number of games a single client can have at the same time, `0` disables the limit
+    pub max_games_per_client: usize,
+}
+
+impl RateLimiter {
+    /// Creates a new rate limiter, routes that are not contained in `limits` use their default limit
+    pub fn new(limits: HashMap<String, Limit>, max_games_per_client: usize) -> Self {
+        Self {
+            limits,
+            buckets: Mutex::new(HashMap::new()),
+            max_games_per_client,
+        }
+    }
+
+    /// Takes one request from the bucket of the client.
+    ///
+    /// # Returns
+    /// `Ok(())` when the request is allowed
+    ///
+    /// `Err(Duration)` the time after which the next request is allowed
+    fn acquire(&self, route: &'static str, default: Limit, client: String) -> Result<(), Duration> {
+        let limit = self.limits.get(route).copied().unwrap_or(default);
+        if limit.capacity == 0 {
+            return Ok(());
+        }
+        self.acquire_at(route, limit, client, Instant::now())
+    }
+
+    /// Takes one request from the bucket of the client at `now` with the limit of the route
+    fn acquire_at(&self, route: &'static str, limit: Limit, client: String, now: Instant) -> Result<(), Duration> {
+        let mut buckets = self.buckets.lock().unwrap();
+        let key = (route, client);
+        if buckets.len() >= MAX_BUCKETS && !buckets.contains_key(&key) {
+            buckets.retain(|_, bucket| !bucket.full(now));
+            if buckets.len() >= MAX_BUCKETS {
+                let mut keys: Vec<_> = buckets.iter().map(|(key, bucket)| (bucket.updated, key.clone())).collect();
+                keys.sort_unstable_by_key(|(updated, _)| *updated);
+                for (_, key) in keys.into_iter().take(buckets.len() + 1 - MAX_BUCKETS) {
+                    buckets.remove(&key);
+                }
+            }
+        }
+        let bucket = buckets.entry(key).or_insert(Bucket { tokens: limit.capacity as f64, updated: now, limit });
+        bucket.limit = limit;
+        let refilled = now.duration_since(bucket.updated).as_secs_f64() / limit.refill_interval().as_secs_f64();
+        bucket.tokens = (bucket.tokens + refilled).min(limit.capacity as f64);
+        bucket.updated = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return Ok(());
+        }
+        Err(limit.refill_interval().mul_f64(1.0 - bucket.tokens))
+    }
+}
+
+/// A route that is protected by a [RateLimit] guard
+pub trait LimitedRoute {
+    /// Name of the limit in the configuration
+    const NAME: &'static str;
+    /// Limit that is used when the route is not configured
+    const DEFAULT: Limit;
+
+    /// The key by which the requests are counted, the client address by default.
+    /// 
+    /// Requests without a key are not limited, otherwise all clients with an unknown address would share one bucket.
+    fn client(request: &Request<'_>) -> Option<String> {
+        request.client_ip().map(|ip| ip.to_string())
+    }
+}
+
+/// Registration of new games, counted per client address
+pub struct Registrations;
+
+impl LimitedRoute for Registrations {
+    const NAME: &'static str = "register";
+    const DEFAULT: Limit = Limit { capacity: 10, seconds: 60 };
+}
+
+/// Guesses, counted per session or per client address when the request has no valid session
+pub struct Guesses;
+
+impl LimitedRoute for Guesses {
+    const NAME: &'static str = "guess";
+    const DEFAULT: Limit = Limit { capacity: 30, seconds: 10 };
+
+    fn client(request: &Request<'_>) -> Option<String> {
+        match request.local_cache(|| None::<PlayerToken>) {
+            Some(token) => Some(token.player_id.to_string()),
+            None => request.client_ip().map(|ip| ip.to_string()),
+        }
+    }
+}
+
+/// Chat messages, counted per session like [Guesses]
+pub struct ChatMessages;
+
+impl LimitedRoute for ChatMessages {
+    const NAME: &'static str = "chat";
+    const DEFAULT: Limit = Limit { capacity: 5, seconds: 10 };
+
+    fn client(request: &Request<'_>) -> Option<String> {
+        Guesses::client(request)
+    }
+}
+
+/// Time after which the client may send the next request, stored in the request local cache for the [too_many_requests] catcher
+struct RetryAfter(Option<Duration>);
+
+/// Request guard that fails with `429 Too Many Requests` when the client has exceeded the limit of the route `R`.
+pub struct RateLimit<R: LimitedRoute>(PhantomData<R>);
+
+#[rocket::async_trait]
+impl<'r, R: LimitedRoute> FromRequest<'r> for RateLimit<R> {
+    type Error = Duration;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let Some(limiter) = request.rocket().state::<RateLimiter>() else {
+            return Outcome::Success(RateLimit(PhantomData));
+        };
+        // Make the verified session available to `LimitedRoute::client`
+        if let Outcome::Success(token) = request.guard::<PlayerToken>().await {
+            request.local_cache(|| Some(token));
+        }
+        let Some(client) = R::client(request) else {
+            return Outcome::Success(RateLimit(PhantomData));
+        };
+        match limiter.acquire(R::NAME, R::DEFAULT, client) {
+            Ok(()) => Outcome::Success(RateLimit(PhantomData)),
+            Err(retry_after) => {
+                request.local_cache(|| RetryAfter(Some(retry_after)));
+                Outcome::Failure((Status::TooManyRequests, retry_after))
+            },
+        }
+    }
+}
+
+impl<'r, R: LimitedRoute> OpenApiFromRequest<'r> for RateLimit<R> {
+    fn from_request_input(_gen: &mut OpenApiGenerator, _name: String, _required: bool) -> rocket_okapi::Result<RequestHeaderInput> {
+        Ok(RequestHeaderInput::None)
+    }
+
+    fn get_responses(_gen: &mut OpenApiGenerator) -> rocket_okapi::Result<Responses> {
+        let mut responses = Responses::default();
+        responses.responses.insert(String::from("429"), RefOr::Object(Response {
+            description: String::from("Too many requests, the `Retry-After` header contains the seconds until the next request is allowed"),
+            ..Default::default()
+        }));
+        Ok(responses)
+    }
+}
+
+/// Response of the [too_many_requests] catcher
+#[derive(Responder)]
+#[response(status = 429)]
+pub struct TooManyRequests {
+    body: (ContentType, String),
+    retry_after: Header<'static>,
+}
+
+/// Tells the client how many seconds it has to wait until the next request is allowed
+#[catch(429)]
+pub fn too_many_requests(request: &Request<'_>) -> TooManyRequests {
+    let seconds = request.local_cache(|| RetryAfter(None)).0.map(|duration| duration.as_secs_f64().ceil() as u64).unwrap_or(1).max(1);
+    TooManyRequests {
+        body: (ContentType::Text, format!("Too many requests, try again in {} seconds", seconds)),
+        retry_after: Header::new("Retry-After", seconds.to_string()),
+    }
+}
+
+/// Response `R` that can tell the client with the `Retry-After` header when it should try again
+pub struct WithRetryAfter<R> {
+    response: R,
+    retry_after: Option<Duration>,
+}
+
+impl<R> WithRetryAfter<R> {
+    /// Adds the `Retry-After` header to the response
+    pub fn new(response: R, retry_after: Duration) -> Self {
+        Self { response, retry_after: Some(retry_after) }
+    }
+}
+
+impl<R> From<R> for WithRetryAfter<R> {
+    fn from(response: R) -> Self {
+        Self { response, retry_after: None }
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for WithRetryAfter<R> {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'o> {
+        let mut response = self.response.respond_to(request)?;
+        if let Some(retry_after) = self.retry_after {
+            response.set_header(Header::new("Retry-After", retry_after.as_secs().max(1).to_string()));
+        }
+        Ok(response)
+    }
+}
+
+impl<R: OpenApiResponderInner> OpenApiResponderInner for WithRetryAfter<R> {
+    fn responses(gen: &mut OpenApiGenerator) -> rocket_okapi::Result<Responses> {
+        R::responses(gen)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, time::{Duration, Instant}};
+
+    use super::{Limit, RateLimiter, MAX_BUCKETS};
+
+    #[test]
+    fn test_acquire() {
+        let limiter = RateLimiter::new(HashMap::new(), 0);
+        let limit = Limit { capacity: 2, seconds: 60 };
+        assert!(limiter.acquire("test", limit, String::from("a")).is_ok());
+        assert!(limiter.acquire("test", limit, String::from("a")).is_ok());
+        let retry_after = limiter.acquire("test", limit, String::from("a")).unwrap_err();
+        assert!(retry_after.as_secs() <= 30 && retry_after.as_secs() >= 29);
+        assert!(limiter.acquire("test", limit, String::from("b")).is_ok());
+    }
+
+    #[test]
+    fn test_max_buckets() {
+        let limiter = RateLimiter::new(HashMap::new(), 0);
+        let limit = Limit { capacity: 2, seconds: 60 };
+        let start = Instant::now();
+        for client in 0..MAX_BUCKETS {
+            let now = start + Duration::from_millis(client as u64);
+            assert!(limiter.acquire_at("test", limit, client.to_string(), now).is_ok());
+        }
+        // No bucket is full again, so the least recently used one is removed
+        let now = start + Duration::from_secs(1);
+        assert!(limiter.acquire_at("test", limit, String::from("new"), now).is_ok());
+        assert_eq!(limiter.buckets.lock().unwrap().len(), MAX_BUCKETS);
+        assert!(!limiter.buckets.lock().unwrap().contains_key(&("test", String::from("0"))));
+        assert!(limiter.buckets.lock().unwrap().contains_key(&("test", String::from("1"))));
+
+        // Buckets are judged by the limit of their own route
+        let short = Limit { capacity: 1, seconds: 1 };
+        assert!(limiter.acquire_at("short", short, String::from("1"), now).is_ok());
+        let now = now + Duration::from_secs(2);
+        assert!(limiter.acquire_at("test", limit, String::from("newer"), now).is_ok());
+        let buckets = limiter.buckets.lock().unwrap();
+        assert!(!buckets.contains_key(&("short", String::from("1"))));
+        assert!(buckets.contains_key(&("test", String::from("2"))));
+    }
+}
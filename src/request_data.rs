@@ -1,18 +1,141 @@
-use std::sync::RwLock;
+use std::{collections::BTreeMap, sync::{Arc, RwLock}, time::Instant};
 
 use rocket::{request::{FromRequest, Outcome}, http::Status};
-use serde::Deserialize;
+use rocket_okapi::{gen::OpenApiGenerator, okapi::openapi3::{Object, SecurityRequirement, SecurityScheme, SecuritySchemeData}, request::{OpenApiFromRequest, RequestHeaderInput}, JsonSchema};
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::{game::GameManager, paths::uuid_from_cookies};
+use crate::{game::GameManager, metrics::Metrics, session::SessionConfig};
+
+/// Name of the cookie that contains the player token
+pub const TOKEN_COOKIE: &str = "uuid";
 
 /// Errors that can occur when the player tries to authenticate a request
 #[derive(Debug)]
 pub enum PlayerAuthError {
-    /// The transmitted id-cookie is missing
+    /// Neither the `Authorization` header nor the id-cookie is set
     Missing,
-    /// The transmitted id-cookie is invalid
+    /// The transmitted token is malformed, has a wrong signature or the player is not assigned to the game anymore
     Invalid,
+    /// The transmitted token has expired
+    Expired,
+}
+
+/// The verified content of the session token of a player.
+/// 
+/// The token is handed out when the player registers. It can be sent in the `uuid` cookie or in the
+/// `Authorization: Bearer <token>` header, both use the same format. The header takes precedence over the cookie.
+/// See [SessionConfig](../session/struct.SessionConfig.html) for the format of the token.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PlayerToken {
+    pub player_id: Uuid,
+    /// The game for which the token was issued
+    pub game_id: Uuid,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for PlayerToken {
+    type Error = PlayerAuthError;
+
+    async fn from_request(request: &'r rocket::Request<'_>) -> Outcome<Self, Self::Error> {
+        let token = match request.headers().get_one("Authorization") {
+            Some(header) => match header.strip_prefix("Bearer ") {
+                Some(token) => token.to_string(),
+                None => return Outcome::Failure((Status::Forbidden, PlayerAuthError::Invalid)),
+            },
+            None => match request.cookies().get(TOKEN_COOKIE) {
+                Some(cookie) => cookie.value().to_string(),
+                None => return Outcome::Failure((Status::Forbidden, PlayerAuthError::Missing)),
+            },
+        };
+        let session_config = request.rocket().state::<SessionConfig>().unwrap();
+        match session_config.verify(&token) {
+            Ok(token) => Outcome::Success(token),
+            Err(pae) => Outcome::Failure((Status::Forbidden, pae)),
+        }
+    }
+}
+
+impl<'r> OpenApiFromRequest<'r> for PlayerToken {
+    fn from_request_input(_gen: &mut OpenApiGenerator, _name: String, _required: bool) -> rocket_okapi::Result<RequestHeaderInput> {
+        Ok(player_token_security())
+    }
+}
+
+/// Documents the player token in the OpenAPI specification
+fn player_token_security() -> RequestHeaderInput {
+    let scheme = SecurityScheme {
+        description: Some(String::from("Token that is returned on registration, can also be sent in the `uuid` cookie")),
+        data: SecuritySchemeData::Http {
+            scheme: String::from("bearer"),
+            bearer_format: None,
+        },
+        extensions: Object::default(),
+    };
+    let mut requirement = SecurityRequirement::new();
+    requirement.insert(String::from("PlayerToken"), Vec::new());
+    RequestHeaderInput::Security(String::from("PlayerToken"), scheme, requirement)
+}
+
+/// The verified content of a spectator token, see [SessionConfig](../session/struct.SessionConfig.html)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SpectatorToken {
+    /// Identifies the spectator, used to count the spectators of a game
+    pub spectator_id: Uuid,
+    /// The game that can be watched with the token
+    pub game_id: Uuid,
+}
+
+/// Symbolizes that the request was sent by a spectator of a game.
+/// 
+/// The spectator token has to be sent in the `token` query parameter, so that the spectator link can be shared. The
+/// authentication fails when the game does not exist anymore or the host has disabled spectating.
+#[derive(Clone, Copy)]
+pub struct SpectatorAuth {
+    pub spectator_id: Uuid,
+    /// The game that is watched
+    pub game_id: Uuid,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for SpectatorAuth {
+    type Error = PlayerAuthError;
+
+    async fn from_request(request: &'r rocket::Request<'_>) -> Outcome<Self, Self::Error> {
+        let Some(Ok(token)) = request.query_value::<&str>("token") else {
+            return Outcome::Failure((Status::Forbidden, PlayerAuthError::Missing));
+        };
+        let session_config = request.rocket().state::<SessionConfig>().unwrap();
+        let token = match session_config.verify_spectator(token) {
+            Ok(token) => token,
+            Err(pae) => return Outcome::Failure((Status::Forbidden, pae)),
+        };
+        let game_manager = request.rocket().state::<Arc<RwLock<GameManager>>>().unwrap();
+        let mut game_manager = match request.rocket().state::<Metrics>() {
+            Some(metrics) => metrics.write(game_manager),
+            None => game_manager.write().unwrap(),
+        };
+        match game_manager.game_by_id(token.game_id) {
+            Some(game) if game.spectating() => Outcome::Success(SpectatorAuth { spectator_id: token.spectator_id, game_id: token.game_id }),
+            _ => Outcome::Failure((Status::Forbidden, PlayerAuthError::Invalid)),
+        }
+    }
+}
+
+impl<'r> OpenApiFromRequest<'r> for SpectatorAuth {
+    fn from_request_input(_gen: &mut OpenApiGenerator, _name: String, _required: bool) -> rocket_okapi::Result<RequestHeaderInput> {
+        let scheme = SecurityScheme {
+            description: Some(String::from("Spectator token that is part of the spectator link")),
+            data: SecuritySchemeData::ApiKey {
+                name: String::from("token"),
+                location: String::from("query"),
+            },
+            extensions: Object::default(),
+        };
+        let mut requirement = SecurityRequirement::new();
+        requirement.insert(String::from("SpectatorToken"), Vec::new());
+        Ok(RequestHeaderInput::Security(String::from("SpectatorToken"), scheme, requirement))
+    }
 }
 
 /// Symbolizes the authentication of a player.
@@ -31,21 +154,426 @@ impl<'r> FromRequest<'r> for PlayerAuth {
     type Error = PlayerAuthError;
 
     async fn from_request(request: &'r rocket::Request<'_>) ->  Outcome<Self, Self::Error> {
-        let uuid = match uuid_from_cookies(request.cookies()) {
-            Ok(uuid) => uuid,
-            Err(pae) => return Outcome::Failure((Status::Forbidden, pae))
+        let token = match PlayerToken::from_request(request).await {
+            Outcome::Success(token) => token,
+            Outcome::Failure(failure) => return Outcome::Failure(failure),
+            Outcome::Forward(data) => return Outcome::Forward(data),
+        };
+        let game_manager = request.rocket().state::<Arc<RwLock<GameManager>>>().unwrap();
+        let mut game_manager = match request.rocket().state::<Metrics>() {
+            Some(metrics) => metrics.write(game_manager),
+            None => game_manager.write().unwrap(),
+        };
+        // The token stays valid only as long as the player is assigned to the game for which it was issued
+        match game_manager.game_by_player_id(token.player_id) {
+            Some(game) if game.game_id() == token.game_id => Outcome::Success(PlayerAuth { player_id: token.player_id, game_id: token.game_id }),
+            _ => Outcome::Failure((Status::Forbidden, PlayerAuthError::Invalid)),
+        }
+    }
+}
+
+/// Response of the registration that contains the token of the new player
+#[derive(Serialize, JsonSchema)]
+pub struct RegistrationData {
+    /// Has to be sent in the `Authorization: Bearer <token>` header or the `uuid` cookie in subsequent requests
+    pub token: String,
+}
+
+/// State of a game as it is returned by the v2 api, only contains the word when the game has ended
+#[derive(Serialize, JsonSchema)]
+pub struct GameView {
+    pub game_id: Uuid,
+    /// The word with the letters that have not been guessed replaced by `_`
+    pub game_string: String,
+    pub lives: i32,
+    pub max_lives: i32,
+    pub guessed_letters: Vec<String>,
+    /// `playing`, `won` or `lost`
+    pub status: String,
+    pub word: Option<String>,
+    /// Only set for turn-based games
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub turn: Option<TurnView>,
+    /// Number of spectators that currently watch the game
+    pub spectators: usize,
+    /// `false` when the host has disabled spectating
+    pub spectating: bool,
+    /// Results of the rounds that have been played in this game
+    pub series: SeriesView,
+    /// Only set for games with time limits
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<TimeView>,
+    /// `true` when the word is not fixed but changes to evade the guesses
+    pub evil: bool,
+}
+
+/// Time that is left in a game with time limits
+#[derive(Serialize, JsonSchema)]
+pub struct TimeView {
+    /// Seconds that are left for the next guess, a life is lost when the time runs out
+    pub guess_seconds_left: Option<u64>,
+    /// Seconds until the game ends
+    pub game_seconds_left: Option<u64>,
+    /// Number of words that have been solved, only set in time-attack games
+    pub score: Option<u32>,
+}
+
+/// Time limits of a new game
+#[derive(Deserialize, JsonSchema)]
+pub struct GameRequest {
+    /// Seconds the player has for each guess, a life is lost every time it runs out. `0` or not set disables the limit.
+    pub guess_time_limit: Option<u64>,
+    /// Seconds after which the game is lost, `0` or not set disables the limit
+    pub game_time_limit: Option<u64>,
+}
+
+/// Settings of a new time-attack game
+#[derive(Deserialize, JsonSchema)]
+pub struct TimeAttackRequest {
+    /// Seconds in which the player solves as many words as possible, defaults to 120 seconds
+    pub duration: Option<u64>,
+}
+
+/// Next guess that the solver suggests for a game
+#[derive(Serialize, JsonSchema)]
+pub struct HintView {
+    /// The best letter or the word when only one word of the words file matches, not set when every letter has been guessed
+    pub guess: Option<String>,
+    /// Number of words of the words file that match the revealed letters and the wrong guesses
+    pub candidates: usize,
+    /// The best letters that have not been guessed yet, the best letter comes first
+    pub letters: Vec<LetterHintView>,
+}
+
+/// Score of a letter that has not been guessed yet
+#[derive(Serialize, JsonSchema)]
+pub struct LetterHintView {
+    pub letter: char,
+    /// Expected information in bits that guessing the letter reveals about the word
+    pub information: f64,
+    /// Share of the matching words that contain the letter
+    pub probability: f64,
+}
+
+/// Settings of a bot that joins a game
+#[derive(Deserialize, JsonSchema)]
+pub struct BotRequest {
+    /// Chance out of 10 that the bot chooses the best guess instead of a random letter, between 1 and 10, defaults to 5
+    pub strength: Option<u8>,
+}
+
+/// Results of the rounds that the players of a game have played in a row
+#[derive(Serialize, JsonSchema)]
+pub struct SeriesView {
+    /// Number of rounds of the series, not set when the rounds are only counted
+    pub best_of: Option<u32>,
+    /// Number of rounds that have ended
+    pub rounds: u32,
+    /// Rounds in which the word was solved
+    pub won: u32,
+    /// Rounds in which the word was not solved
+    pub lost: u32,
+    /// Rounds that each player has won in the order of the player numbers, only set for turn-based games
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub wins: Vec<u32>,
+    /// `true` when the series has been decided, the next round starts a new series
+    pub finished: bool,
+}
+
+/// Settings of a rematch
+#[derive(Deserialize, JsonSchema)]
+pub struct RematchRequest {
+    /// Starts a new best-of series with that many rounds, the current series is continued when not set
+    pub best_of: Option<u32>,
+}
+
+/// State of a game as it is shown to spectators, contains the same information as the players see
+#[derive(Serialize, JsonSchema)]
+pub struct SpectatorView {
+    #[serde(flatten)]
+    pub game: GameView,
+    /// All guesses in the order in which they were submitted
+    pub guesses: Vec<String>,
+}
+
+/// Response of the v2 api when a spectator link has been created
+#[derive(Serialize, JsonSchema)]
+pub struct SpectatorLink {
+    /// Spectator token that can only be used to watch the game
+    pub token: String,
+    /// Path under which the game can be watched, contains the token
+    pub url: String,
+}
+
+/// Body with which the host allows or forbids spectating
+#[derive(Deserialize, JsonSchema)]
+pub struct SpectatingRequest {
+    pub enabled: bool,
+}
+
+/// Order of the players in a turn-based game as returned by the v2 api.
+///
+/// The players are numbered starting at 1 in the order in which they joined, the only player of other games has the number 1.
+#[derive(Serialize, JsonSchema)]
+pub struct TurnView {
+    /// `0` until the first word has been chosen
+    pub round: u32,
+    /// Number of the player that sent the request
+    pub you: usize,
+    pub players: usize,
+    /// The player that chooses the word of the current round or of the next round once the round has ended
+    pub chooser: usize,
+    /// The player that has to guess now, only set while the round is running
+    pub current: Option<usize>,
+    /// Seconds until the current player is skipped
+    pub seconds_left: Option<u64>,
+    /// `true` when the chosen words have to be contained in the words file
+    pub dictionary: bool,
+}
+
+/// Settings of a new turn-based game
+#[derive(Deserialize, JsonSchema)]
+pub struct TurnGameRequest {
+    /// Seconds a player has for a guess before the turn is passed on, at most one hour. `0` disables the timeout.
+    /// Defaults to 60 seconds.
+    pub turn_timeout: Option<u64>,
+    /// `true` when the chosen words have to be contained in the words file, defaults to `false`
+    #[serde(default)]
+    pub dictionary: bool,
+}
+
+/// Word of the next round of a turn-based game
+#[derive(Deserialize, JsonSchema)]
+pub struct WordRequest {
+    pub word: String,
+}
+
+/// Response of the v2 api when a game has been created
+#[derive(Serialize, JsonSchema)]
+pub struct CreatedGame {
+    /// Has to be sent in the `Authorization: Bearer <token>` header or the `uuid` cookie in subsequent requests
+    pub token: String,
+    #[serde(flatten)]
+    pub game: GameView,
+}
+
+/// Body of a guess that is submitted to the v2 api
+#[derive(Deserialize, JsonSchema)]
+pub struct GuessRequest {
+    /// A letter or a whole word
+    pub guess: String,
+}
+
+/// Response of the v2 api to a guess
+#[derive(Serialize, JsonSchema)]
+pub struct GuessResponse {
+    /// The result of [Game::guess](../game/base_game/struct.Game.html#method.guess)
+    pub result: i32,
+    /// The name of the result, see [GUESS_OUTCOMES](../game/guess/constant.GUESS_OUTCOMES.html)
+    pub outcome: String,
+    pub game: GameView,
+}
+
+/// Player of a game as returned by the v2 api
+#[derive(Serialize, JsonSchema)]
+pub struct PlayerView {
+    pub name: String,
+    /// `true` for the player that sent the request
+    pub you: bool,
+}
+
+/// Response of the v2 api when a race has been created or joined, contains the game of the player in the race
+#[derive(Serialize, JsonSchema)]
+pub struct JoinedRace {
+    /// Has to be sent in the `Authorization: Bearer <token>` header or the `uuid` cookie in subsequent requests
+    pub token: String,
+    /// Other players can join the race with this id
+    pub race_id: Uuid,
+    #[serde(flatten)]
+    pub game: GameView,
+}
+
+/// Progress of a player in a race as returned by the v2 api, the guessed letters are not visible to the other players
+#[derive(Serialize, JsonSchema)]
+pub struct RacerView {
+    /// Number of the player, starting at 1 in the order in which the players joined
+    pub player: usize,
+    /// `true` for the player that sent the request
+    pub you: bool,
+    /// Number of letters that have been revealed
+    pub revealed: usize,
+    /// Number of letters of the word
+    pub letters: usize,
+    pub lives: i32,
+    /// `playing`, `won` or `lost`
+    pub status: String,
+}
+
+/// State of a race as returned by the v2 api
+#[derive(Serialize, JsonSchema)]
+pub struct RaceView {
+    pub race_id: Uuid,
+    /// `running` or `finished`
+    pub status: String,
+    /// Only set when the race has finished
+    pub word: Option<String>,
+    pub players: Vec<RacerView>,
+    /// Numbers of the players that won, empty while the race is running
+    pub winners: Vec<usize>,
+    /// `true` when several players won because nobody solved the word and they revealed the same number of letters
+    pub tie: bool,
+}
+
+/// Chat message as returned by the v2 api
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct ChatMessageView {
+    /// Number of the player that sent the message, see [TurnView]
+    pub player: usize,
+    pub text: String,
+    /// Unix time in seconds at which the message was sent
+    pub sent: u64,
+}
+
+/// Body of a chat message that is sent to the v2 api
+#[derive(Deserialize, JsonSchema)]
+pub struct ChatRequest {
+    pub text: String,
+}
+
+/// Error that is returned by the v2 api
+#[derive(Serialize, JsonSchema)]
+pub struct ApiError {
+    pub error: String,
+}
+
+impl<'r> OpenApiFromRequest<'r> for PlayerAuth {
+    fn from_request_input(_gen: &mut OpenApiGenerator, _name: String, _required: bool) -> rocket_okapi::Result<RequestHeaderInput> {
+        Ok(player_token_security())
+    }
+}
+
+/// Configuration of the admin api
+pub struct AdminConfig {
+    /// The token that has to be sent in the `X-Admin-Token` header to access the admin api.
+    /// 
+    /// When no token is configured the admin api is disabled.
+    pub token: Option<String>,
+}
+
+/// Errors that can occur when a request to the admin api is authenticated
+#[derive(Debug)]
+pub enum AdminAuthError {
+    /// No admin token is configured, the admin api is disabled
+    Disabled,
+    /// The `X-Admin-Token` header is missing
+    Missing,
+    /// The transmitted token is wrong
+    Invalid,
+}
+
+/// Symbolizes that the request was sent by an administrator.
+/// 
+/// The request has to contain the configured admin token in the `X-Admin-Token` header.
+/// The player cookie is not used for this authentication.
+pub struct AdminAuth;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for AdminAuth {
+    type Error = AdminAuthError;
+
+    async fn from_request(request: &'r rocket::Request<'_>) -> Outcome<Self, Self::Error> {
+        let configured = match request.rocket().state::<AdminConfig>().and_then(|config| config.token.as_ref()) {
+            Some(token) => token,
+            None => return Outcome::Failure((Status::Forbidden, AdminAuthError::Disabled)),
         };
-        let mut game_manager = request.rocket().state::<RwLock<GameManager>>().unwrap().write().unwrap();
-        let game = match game_manager.game_by_player_id(uuid) {
-            Some(game) => game,
-            None => return Outcome::Failure((Status::Forbidden, PlayerAuthError::Invalid)),
+        match request.headers().get_one("X-Admin-Token") {
+            Some(token) if constant_time_eq(token.as_bytes(), configured.as_bytes()) => Outcome::Success(AdminAuth),
+            Some(_) => Outcome::Failure((Status::Forbidden, AdminAuthError::Invalid)),
+            None => Outcome::Failure((Status::Forbidden, AdminAuthError::Missing)),
+        }
+    }
+}
+
+impl<'r> OpenApiFromRequest<'r> for AdminAuth {
+    fn from_request_input(_gen: &mut OpenApiGenerator, _name: String, _required: bool) -> rocket_okapi::Result<RequestHeaderInput> {
+        let scheme = SecurityScheme {
+            description: Some(String::from("The admin token that is configured with `admin_token`")),
+            data: SecuritySchemeData::ApiKey {
+                name: String::from("X-Admin-Token"),
+                location: String::from("header"),
+            },
+            extensions: Object::default(),
         };
-        Outcome::Success(PlayerAuth { player_id: uuid, game_id: game.game_id()})
+        let mut requirement = SecurityRequirement::new();
+        requirement.insert(String::from("AdminToken"), Vec::new());
+        Ok(RequestHeaderInput::Security(String::from("AdminToken"), scheme, requirement))
+    }
+}
+
+/// Compares the two byte slices in a time that does not depend on the position of the first difference
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
     }
+    a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Overview of a game that is returned by the admin api
+#[derive(Serialize, JsonSchema)]
+pub struct GameInfo {
+    pub game_id: Uuid,
+    /// Seconds since the game was created
+    pub age: u64,
+    pub lives: i32,
+    /// `playing`, `won` or `lost`
+    pub status: String,
+    pub players: usize,
 }
 
-/// Used to get the character from a request formatted as json
-#[derive(Deserialize)]
-pub struct Character {
-    pub character: char,
-}
\ No newline at end of file
+/// Details of a game that are returned by the admin api, contains the solution
+#[derive(Serialize, JsonSchema)]
+pub struct GameDetails {
+    #[serde(flatten)]
+    pub info: GameInfo,
+    pub word: String,
+    pub game_string: String,
+    /// All guesses in the order in which they were submitted
+    pub history: Vec<String>,
+}
+
+/// Counts of the game manager that are returned by the admin api
+#[derive(Serialize, JsonSchema)]
+pub struct ManagerStats {
+    pub active_games: usize,
+    pub max_active_games: usize,
+    pub words: usize,
+}
+
+/// The time at which the server was started, used to calculate the uptime
+pub struct ServerStart(pub Instant);
+
+/// Response of the `/healthz` endpoint
+#[derive(Serialize, JsonSchema)]
+pub struct Health {
+    /// `ok` or `unhealthy`
+    pub status: String,
+    /// Seconds since the server was started
+    pub uptime: u64,
+    /// `ok` or `poisoned` when a thread panicked while holding the game manager lock
+    pub lock: String,
+}
+
+/// Response of the `/readyz` endpoint
+#[derive(Serialize, JsonSchema)]
+pub struct Readiness {
+    /// `true` when new games can be registered
+    pub ready: bool,
+    #[serde(flatten)]
+    pub health: Health,
+    /// Number of words that are loaded per dictionary
+    pub dictionaries: BTreeMap<String, usize>,
+    /// Where the games are stored, games are currently only kept in memory
+    pub persistence: String,
+    pub active_games: usize,
+    pub max_active_games: usize,
+}
@@ -0,0 +1,187 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use rocket::http::{Cookie, SameSite};
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::request_data::{PlayerAuthError, PlayerToken, SpectatorToken, TOKEN_COOKIE};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Session lifetime in seconds that is used when `session_lifetime` is not configured
+pub const DEFAULT_SESSION_LIFETIME: u64 = 24 * 60 * 60;
+
+/// Issues and verifies the session tokens of the players.
+///
+/// A token has the format `<payload>.<signature>`, both parts are base64url encoded. The payload contains the player id,
+/// the game id and the unix time at which the token expires, separated by `:`. The signature is a HMAC-SHA256 of the
+/// payload with the session secret.
+///
+/// Spectator tokens use the same format, their payload starts with `spectator` followed by the spectator id, the game id
+/// and the expiry. They can therefore not be used as player tokens.
+pub struct SessionConfig {
+    secret: Vec<u8>,
+    /// Time after which an issued token expires
+    lifetime: Duration,
+    /// Sets the `Secure` attribute on the session cookie, should be enabled when the server is reached by https
+    secure_cookies: bool,
+}
+
+impl SessionConfig {
+    /// Creates a new session configuration.
+    ///
+    /// When no secret is set a random secret is generated, tokens are then invalidated when the server restarts.
+    pub fn new(secret: Option<String>, lifetime: Duration, secure_cookies: bool) -> Self {
+        let secret = match secret {
+            Some(secret) => secret.into_bytes(),
+            None => {
+                let mut secret = vec![0; 32];
+                rand::thread_rng().fill_bytes(&mut secret);
+                secret
+            },
+        };
+        Self { secret, lifetime, secure_cookies }
+    }
+
+    /// Issues a new signed token for the player in the game
+    pub fn issue(&self, player_id: Uuid, game_id: Uuid) -> String {
+        self.sign(format!("{}:{}:{}", player_id, game_id, unix_time() + self.lifetime.as_secs()))
+    }
+
+    /// Issues a new signed token with which the spectator can watch the game
+    pub fn issue_spectator(&self, spectator_id: Uuid, game_id: Uuid) -> String {
+        self.sign(format!("spectator:{}:{}:{}", spectator_id, game_id, unix_time() + self.lifetime.as_secs()))
+    }
+
+    /// Verifies the signature and the expiry of the token.
+    ///
+    /// # Returns
+    /// `Ok(PlayerToken)` the content of the token when it is valid
+    ///
+    /// `Err(PlayerAuthError)` when the token is malformed, the signature does not match or the token has expired
+    pub fn verify(&self, token: &str) -> Result<PlayerToken, PlayerAuthError> {
+        let payload = self.open(token)?;
+        let mut parts = payload.split(':');
+        let (Some(player_id), Some(game_id), Some(expires), None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+            return Err(PlayerAuthError::Invalid);
+        };
+        check_expiry(expires)?;
+        Ok(PlayerToken {
+            player_id: parse_id(player_id)?,
+            game_id: parse_id(game_id)?,
+        })
+    }
+
+    /// Verifies the signature and the expiry of the spectator token like [verify](#method.verify).
+    pub fn verify_spectator(&self, token: &str) -> Result<SpectatorToken, PlayerAuthError> {
+        let payload = self.open(token)?;
+        let mut parts = payload.split(':');
+        let (Some("spectator"), Some(spectator_id), Some(game_id), Some(expires), None) = (parts.next(), parts.next(), parts.next(), parts.next(), parts.next()) else {
+            return Err(PlayerAuthError::Invalid);
+        };
+        check_expiry(expires)?;
+        Ok(SpectatorToken {
+            spectator_id: parse_id(spectator_id)?,
+            game_id: parse_id(game_id)?,
+        })
+    }
+
+    /// Encodes the payload and appends the signature
+    fn sign(&self, payload: String) -> String {
+        let payload = URL_SAFE_NO_PAD.encode(payload);
+        let signature = URL_SAFE_NO_PAD.encode(self.mac(&payload).finalize().into_bytes());
+        format!("{}.{}", payload, signature)
+    }
+
+    /// Verifies the signature of the token
+    /// # Returns
+    /// The decoded payload
+    fn open(&self, token: &str) -> Result<String, PlayerAuthError> {
+        let (payload, signature) = token.trim().split_once('.').ok_or(PlayerAuthError::Invalid)?;
+        let signature = URL_SAFE_NO_PAD.decode(signature).map_err(|_err| PlayerAuthError::Invalid)?;
+        self.mac(payload).verify_slice(&signature).map_err(|_err| PlayerAuthError::Invalid)?;
+        let payload = URL_SAFE_NO_PAD.decode(payload).map_err(|_err| PlayerAuthError::Invalid)?;
+        String::from_utf8(payload).map_err(|_err| PlayerAuthError::Invalid)
+    }
+
+    /// Creates the cookie that stores the token in the browser
+    pub fn cookie(&self, token: String) -> Cookie<'static> {
+        Cookie::build(TOKEN_COOKIE, token)
+            .path("/")
+            .http_only(true)
+            .same_site(SameSite::Lax)
+            .secure(self.secure_cookies)
+            .max_age(rocket::time::Duration::seconds(self.lifetime.as_secs() as i64))
+            .finish()
+    }
+
+    fn mac(&self, payload: &str) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any length");
+        mac.update(payload.as_bytes());
+        mac
+    }
+}
+
+/// Fails with `Expired` when the unix time `expires` has passed
+fn check_expiry(expires: &str) -> Result<(), PlayerAuthError> {
+    let expires: u64 = expires.parse().map_err(|_err| PlayerAuthError::Invalid)?;
+    match expires <= unix_time() {
+        true => Err(PlayerAuthError::Expired),
+        false => Ok(()),
+    }
+}
+
+fn parse_id(id: &str) -> Result<Uuid, PlayerAuthError> {
+    Uuid::parse_str(id).map_err(|_err| PlayerAuthError::Invalid)
+}
+
+/// Seconds since the unix epoch
+fn unix_time() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use uuid::Uuid;
+
+    use crate::request_data::PlayerAuthError;
+
+    use super::SessionConfig;
+
+    #[test]
+    fn test_verify() {
+        let config = SessionConfig::new(Some(String::from("secret")), Duration::from_secs(60), false);
+        let player_id = Uuid::new_v4();
+        let game_id = Uuid::new_v4();
+        let token = config.issue(player_id, game_id);
+        let verified = config.verify(&token).unwrap();
+        assert_eq!(verified.player_id, player_id);
+        assert_eq!(verified.game_id, game_id);
+
+        let other = SessionConfig::new(Some(String::from("other")), Duration::from_secs(60), false);
+        assert!(matches!(other.verify(&token), Err(PlayerAuthError::Invalid)));
+        assert!(matches!(config.verify(&player_id.to_string()), Err(PlayerAuthError::Invalid)));
+
+        let expired = SessionConfig::new(Some(String::from("secret")), Duration::ZERO, false);
+        assert!(matches!(expired.verify(&expired.issue(player_id, game_id)), Err(PlayerAuthError::Expired)));
+    }
+
+    #[test]
+    fn test_verify_spectator() {
+        let config = SessionConfig::new(Some(String::from("secret")), Duration::from_secs(60), false);
+        let spectator_id = Uuid::new_v4();
+        let game_id = Uuid::new_v4();
+        let token = config.issue_spectator(spectator_id, game_id);
+        let verified = config.verify_spectator(&token).unwrap();
+        assert_eq!(verified.spectator_id, spectator_id);
+        assert_eq!(verified.game_id, game_id);
+        // Spectator and player tokens can not be mixed up
+        assert!(matches!(config.verify(&token), Err(PlayerAuthError::Invalid)));
+        assert!(matches!(config.verify_spectator(&config.issue(spectator_id, game_id)), Err(PlayerAuthError::Invalid)));
+    }
+}
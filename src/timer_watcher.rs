@@ -0,0 +1,75 @@
+use std::{sync::{Arc, RwLock}, time::{Duration, Instant}};
+
+use rocket::{fairing::{Fairing, Info, Kind}, tokio, Orbit, Rocket};
+
+use crate::{game::{bot::BotTurn, GameManager}, metrics::{GuessCounter, Metrics}};
+
+/// Fairing that enforces the time limits of all games and lets the bots move periodically.
+///
+/// The limits are also checked whenever a game is accessed, the watcher ends the games of players that have stopped
+/// playing so that they are shown as ended in the admin api and the metrics. Bots only move when the watcher runs, their
+/// moves are computed while the game manager is not locked and the guesses are counted in the [Metrics].
+pub struct TimerWatcher {
+    /// Time between two checks, the watcher is disabled when this is zero
+    pub interval: Duration,
+}
+
+#[rocket::async_trait]
+impl Fairing for TimerWatcher {
+    fn info(&self) -> Info {
+        Info {
+            name: "Timer watcher",
+            kind: Kind::Liftoff,
+        }
+    }
+
+    async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
+        if self.interval.is_zero() {
+            return;
+        }
+        let Some(game_manager) = rocket.state::<Arc<RwLock<GameManager>>>() else {
+            return;
+        };
+        let game_manager = Arc::clone(game_manager);
+        let guesses = rocket.state::<Metrics>().map(Metrics::guess_counter);
+        let mut interval = tokio::time::interval(self.interval);
+        tokio::spawn(async move {
+            loop {
+                interval.tick().await;
+                let game_manager = Arc::clone(&game_manager);
+                let guesses = guesses.clone();
+                // The solver and the blocking lock must not stall the workers that handle the requests
+                if let Err(err) = tokio::task::spawn_blocking(move || tick(&game_manager, guesses.as_deref())).await {
+                    error!("Timer watcher failed: {}", err);
+                }
+            }
+        });
+    }
+}
+
+/// Ends the games whose time limits are exceeded and lets the bots move
+fn tick(game_manager: &RwLock<GameManager>, guesses: Option<&GuessCounter>) {
+    let now = Instant::now();
+    let turns = match game_manager.write() {
+        Ok(mut game_manager) => {
+            game_manager.check_timers();
+            game_manager.bot_turns(now)
+        },
+        Err(err) => {
+            error!("Timer watcher failed: {}", err);
+            return;
+        },
+    };
+    // The solver runs without holding the lock
+    let moves = turns.into_iter().filter_map(BotTurn::play).collect();
+    let results = match game_manager.write() {
+        Ok(mut game_manager) => game_manager.apply_bot_moves(now, moves),
+        Err(err) => {
+            error!("Timer watcher failed: {}", err);
+            return;
+        },
+    };
+    if let Some(guesses) = guesses {
+        results.into_iter().for_each(|result| guesses.record(result));
+    }
+}
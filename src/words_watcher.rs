@@ -0,0 +1,48 @@
+use std::{sync::{Arc, RwLock}, time::Duration};
+
+use rocket::{fairing::{Fairing, Info, Kind}, tokio, Orbit, Rocket};
+
+use crate::game::GameManager;
+
+/// Fairing that checks the words file periodically and reloads it when it was modified.
+///
+/// Games that are already running keep their word, new games use the reloaded words.
+/// When the file can not be loaded the error is logged and the previous words are kept.
+pub struct WordsWatcher {
+    /// Time between two checks of the words file, the watcher is disabled when this is zero
+    pub interval: Duration,
+}
+
+#[rocket::async_trait]
+impl Fairing for WordsWatcher {
+    fn info(&self) -> Info {
+        Info {
+            name: "Words watcher",
+            kind: Kind::Liftoff,
+        }
+    }
+
+    async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
+        if self.interval.is_zero() {
+            return;
+        }
+        let Some(game_manager) = rocket.state::<Arc<RwLock<GameManager>>>() else {
+            return;
+        };
+        let words = game_manager.read().unwrap().words();
+        let mut interval = tokio::time::interval(self.interval);
+        tokio::spawn(async move {
+            loop {
+                interval.tick().await;
+                let source = Arc::clone(&words);
+                let result = tokio::task::spawn_blocking(move || source.reload_if_changed()).await;
+                match result {
+                    Ok(Ok(Some(count))) => info!("Reloaded {} words from {}", count, words.path().display()),
+                    Ok(Ok(None)) => (),
+                    Ok(Err(err)) => error!("Unable to reload {}, keeping the previous words: {}", words.path().display(), err),
+                    Err(err) => error!("Words watcher failed: {}", err),
+                }
+            }
+        });
+    }
+}
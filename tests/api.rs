@@ -0,0 +1,495 @@
+//! Drives complete games through the http api of the server
+
+use std::{fs, net::SocketAddr, path::PathBuf};
+
+use hangman_rs_web::{build_rocket_with_words, game::word_list::WordSource};
+use rocket::{http::{ContentType, Header, Status}, local::blocking::{Client, LocalResponse}, serde::json::{json, Value}};
+
+/// The only word of the test servers, so that the outcome of the guesses is known
+const WORD: &str = "HUND";
+
+/// Words file that only contains [WORD]
+fn words(name: &str) -> WordSource {
+    let path: PathBuf = std::env::temp_dir().join(format!("hangman_rs_web_{}_{}.txt", name, std::process::id()));
+    fs::write(&path, WORD).unwrap();
+    WordSource::load(&path).unwrap()
+}
+
+/// Starts a test server that only knows [WORD]. The client keeps the cookies between requests like a browser.
+fn client(name: &str) -> Client {
+    Client::tracked(build_rocket_with_words(words(name))).unwrap()
+}
+
+fn text(response: LocalResponse<'_>) -> String {
+    response.into_string().unwrap()
+}
+
+fn guess<'c>(client: &'c Client, guess: &str) -> LocalResponse<'c> {
+    client.post("/api/guess").header(ContentType::JSON).body(json!(guess).to_string()).dispatch()
+}
+
+#[test]
+fn test_win_game() {
+    let client = client("win");
+    assert_eq!(text(client.get("/api/registered").dispatch()), "false");
+    let response = client.post("/api/register").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert!(response.cookies().get("uuid").is_some());
+    let body: Value = response.into_json().unwrap();
+    assert!(body["token"].is_string());
+    assert_eq!(text(client.get("/api/registered").dispatch()), "playing");
+    assert_eq!(text(client.get("/api/game_string").dispatch()), "_ _ _ _");
+    assert_eq!(text(client.get("/api/max_lives").dispatch()), "7");
+
+    assert_eq!(text(guess(&client, "h")), "2");
+    assert_eq!(text(guess(&client, "H")), "5");
+    assert_eq!(text(guess(&client, "X")), "3");
+    assert_eq!(text(client.get("/api/lives").dispatch()), "6");
+    assert_eq!(text(client.get("/api/game_string").dispatch()), "H _ _ _");
+    assert_eq!(text(client.get("/api/guessed_letters").dispatch()).split_whitespace().collect::<String>(), "HX");
+    assert_eq!(text(client.get("/api/word").dispatch()), "Unable to return word: Game has to end first!");
+
+    assert_eq!(text(guess(&client, "Hund")), "1");
+    assert_eq!(text(client.get("/api/registered").dispatch()), "won");
+    assert_eq!(text(client.get("/api/word").dispatch()), WORD);
+}
+
+#[test]
+fn test_lose_game() {
+    let client = client("lose");
+    client.post("/api/register").dispatch();
+    for letter in ["A", "B", "C", "E", "F", "G"] {
+        assert_eq!(text(guess(&client, letter)), "3");
+    }
+    assert_eq!(text(guess(&client, "I")), "4");
+    assert_eq!(text(client.get("/api/lives").dispatch()), "0");
+    assert_eq!(text(client.get("/api/registered").dispatch()), "lost");
+    assert_eq!(text(client.get("/api/word").dispatch()), WORD);
+}
+
+#[test]
+fn test_invalid_guess() {
+    let client = client("invalid");
+    client.post("/api/register").dispatch();
+    for input in ["", "1", "Ä", "Hu nd"] {
+        let response = guess(&client, input);
+        assert_eq!(response.status(), Status::BadRequest, "{:?}", input);
+    }
+    assert_eq!(text(client.get("/api/lives").dispatch()), "7");
+}
+
+#[test]
+fn test_restore_game() {
+    let client = client("restore");
+    let token = client.post("/api/register").dispatch().into_json::<Value>().unwrap()["token"].as_str().unwrap().to_string();
+    guess(&client, "U");
+    // A reload of the page only sends the cookie again
+    assert_eq!(text(client.get("/api/registered").dispatch()), "playing");
+    assert_eq!(text(client.get("/api/game_string").dispatch()), "_ U _ _");
+    // Clients without cookies continue the game with the token, the header takes precedence over the cookie
+    let response = client.get("/api/game_string").header(Header::new("Authorization", format!("Bearer {}", token))).dispatch();
+    assert_eq!(text(response), "_ U _ _");
+}
+
+#[test]
+fn test_delete_game() {
+    let client = client("delete");
+    let token = client.post("/api/register").dispatch().into_json::<Value>().unwrap()["token"].as_str().unwrap().to_string();
+    let response = client.get("/api/delete_game").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.headers().get_one("Deprecation"), Some("true"));
+    assert_eq!(text(client.get("/api/registered").dispatch()), "false");
+    assert_eq!(client.get("/api/lives").dispatch().status(), Status::Forbidden);
+    let response = client.get("/api/lives").header(Header::new("Authorization", format!("Bearer {}", token))).dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+}
+
+#[test]
+fn test_authentication() {
+    let client = client("auth");
+    assert_eq!(client.get("/api/lives").dispatch().status(), Status::Forbidden);
+    assert_eq!(guess(&client, "A").status(), Status::Forbidden);
+    let response = client.get("/api/lives").header(Header::new("Authorization", "Bearer invalid")).dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+    assert_eq!(client.get("/api/admin/stats").dispatch().status(), Status::Forbidden);
+}
+
+#[test]
+fn test_v2_game() {
+    let client = client("v2");
+    let response = client.post("/api/v2/games").dispatch();
+    assert_eq!(response.status(), Status::Created);
+    let location = response.headers().get_one("Location").unwrap().to_string();
+    let body: Value = response.into_json().unwrap();
+    assert_eq!(location, format!("/api/v2/games/{}", body["game_id"].as_str().unwrap()));
+    assert_eq!(body["game_string"], "_ _ _ _");
+    assert_eq!(body["lives"], 7);
+    assert_eq!(body["status"], "playing");
+    assert_eq!(body["word"], Value::Null);
+
+    let response = client.post(format!("{}/guesses", location)).json(&json!({ "guess": "n" })).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().unwrap();
+    assert_eq!(body["result"], 2);
+    assert_eq!(body["outcome"], "correct");
+    assert_eq!(body["game"]["game_string"], "_ _ N _");
+    assert_eq!(body["game"]["guessed_letters"], json!(["N"]));
+
+    let response = client.post(format!("{}/guesses", location)).json(&json!({ "guess": "!" })).dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    assert!(response.into_json::<Value>().unwrap()["error"].is_string());
+
+    let body: Value = client.post(format!("{}/guesses", location)).json(&json!({ "guess": "hund" })).dispatch().into_json().unwrap();
+    assert_eq!(body["outcome"], "solved");
+    assert_eq!(body["game"]["status"], "won");
+    assert_eq!(body["game"]["word"], WORD);
+
+    let players: Value = client.get(format!("{}/players", location)).dispatch().into_json().unwrap();
+    assert_eq!(players, json!([{ "name": "steve", "you": true }]));
+    let other = format!("/api/v2/games/{}", uuid::Uuid::new_v4());
+    assert_eq!(client.get(other).dispatch().status(), Status::Forbidden);
+
+    assert_eq!(client.delete(location.as_str()).dispatch().status(), Status::NoContent);
+    assert_eq!(client.get(location).dispatch().status(), Status::Forbidden);
+}
+
+#[test]
+fn test_race() {
+    // Each player sends the own token, so the cookies are not kept
+    let client = Client::untracked(build_rocket_with_words(words("race"))).unwrap();
+    let bearer = |body: &Value| Header::new("Authorization", format!("Bearer {}", body["token"].as_str().unwrap()));
+
+    let response = client.post("/api/v2/races").dispatch();
+    assert_eq!(response.status(), Status::Created);
+    let location = response.headers().get_one("Location").unwrap().to_string();
+    let first: Value = response.into_json().unwrap();
+    assert_eq!(location, format!("/api/v2/races/{}", first["race_id"].as_str().unwrap()));
+    let response = client.post(format!("{}/players", location)).dispatch();
+    assert_eq!(response.status(), Status::Created);
+    let second: Value = response.into_json().unwrap();
+    assert_eq!(second["game_string"], "_ _ _ _");
+
+    let guess_url = |player: &Value| format!("/api/v2/games/{}/guesses", player["game_id"].as_str().unwrap());
+    client.post(guess_url(&first)).header(bearer(&first)).json(&json!({ "guess": "H" })).dispatch();
+    let race: Value = client.get(location.as_str()).header(bearer(&second)).dispatch().into_json().unwrap();
+    assert_eq!(race["status"], "running");
+    assert_eq!(race["word"], Value::Null);
+    assert_eq!(race["players"], json!([
+        { "player": 1, "you": false, "revealed": 1, "letters": 4, "lives": 7, "status": "playing" },
+        { "player": 2, "you": true, "revealed": 0, "letters": 4, "lives": 7, "status": "playing" },
+    ]));
+
+    let body: Value = client.post(guess_url(&second)).header(bearer(&second)).json(&json!({ "guess": "Hund" })).dispatch().into_json().unwrap();
+    assert_eq!(body["game"]["status"], "won");
+    let race: Value = client.get(location.as_str()).header(bearer(&first)).dispatch().into_json().unwrap();
+    assert_eq!(race["status"], "finished");
+    assert_eq!(race["word"], WORD);
+    assert_eq!(race["winners"], json!([2]));
+    assert_eq!(race["tie"], false);
+    assert_eq!(race["players"][0]["status"], "lost");
+
+    assert_eq!(client.post(format!("{}/players", location)).dispatch().status(), Status::Conflict);
+    let other = client.post("/api/v2/races").dispatch().into_json::<Value>().unwrap();
+    assert_eq!(client.get(location.as_str()).header(bearer(&other)).dispatch().status(), Status::Forbidden);
+    assert_eq!(client.post(format!("/api/v2/races/{}/players", uuid::Uuid::new_v4())).dispatch().status(), Status::NotFound);
+
+    // Players of other games can not find out whether a game is part of a race
+    let hint_url = format!("/api/v2/games/{}/hint", first["game_id"].as_str().unwrap());
+    assert_eq!(client.get(hint_url.as_str()).header(bearer(&other)).dispatch().status(), Status::Forbidden);
+    assert_eq!(client.get(hint_url.as_str()).header(bearer(&first)).dispatch().status(), Status::Conflict);
+}
+
+#[test]
+fn test_turn_game() {
+    let client = Client::untracked(build_rocket_with_words(words("turns"))).unwrap();
+    let bearer = |body: &Value| Header::new("Authorization", format!("Bearer {}", body["token"].as_str().unwrap()));
+
+    let response = client.post("/api/v2/turn_games").json(&json!({ "turn_timeout": 3601 })).dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    let response = client.post("/api/v2/turn_games").json(&json!({ "turn_timeout": 0 })).dispatch();
+    assert_eq!(response.status(), Status::Created);
+    let location = response.headers().get_one("Location").unwrap().to_string();
+    let host: Value = response.into_json().unwrap();
+    assert_eq!(host["status"], "choosing");
+    assert_eq!(host["turn"], json!({ "round": 0, "you": 1, "players": 1, "chooser": 1, "current": null, "seconds_left": null, "dictionary": false }));
+    let guest: Value = client.post(format!("{}/players", location)).dispatch().into_json().unwrap();
+    assert_eq!(guest["game_id"], host["game_id"]);
+    assert_eq!(guest["turn"]["you"], 2);
+
+    let response = client.put(format!("{}/word", location)).header(bearer(&guest)).json(&json!({ "word": "Katze" })).dispatch();
+    assert_eq!(response.status(), Status::Conflict);
+    let response = client.put(format!("{}/word", location)).header(bearer(&host)).json(&json!({ "word": "Kat ze" })).dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    let body: Value = client.put(format!("{}/word", location)).header(bearer(&host)).json(&json!({ "word": "Katze" })).dispatch().into_json().unwrap();
+    assert_eq!(body["game_string"], "_ _ _ _ _");
+    assert_eq!(body["turn"]["current"], 2);
+    assert_eq!(client.post(format!("{}/players", location)).dispatch().status(), Status::Conflict);
+
+    let response = client.post(format!("{}/guesses", location)).header(bearer(&host)).json(&json!({ "guess": "A" })).dispatch();
+    assert_eq!(response.status(), Status::Conflict);
+    let body: Value = client.post(format!("{}/guesses", location)).header(bearer(&guest)).json(&json!({ "guess": "katze" })).dispatch().into_json().unwrap();
+    assert_eq!(body["game"]["status"], "won");
+    assert_eq!(body["game"]["turn"]["chooser"], 2);
+    let body: Value = client.put(format!("{}/word", location)).header(bearer(&guest)).json(&json!({ "word": "Hund" })).dispatch().into_json().unwrap();
+    assert_eq!(body["turn"]["round"], 2);
+    assert_eq!(body["turn"]["current"], 1);
+    assert_eq!(client.post("/api/v2/games/00000000-0000-0000-0000-000000000000/players").dispatch().status(), Status::NotFound);
+}
+
+#[test]
+fn test_register_again_keeps_shared_game() {
+    let client = Client::untracked(build_rocket_with_words(words("leave"))).unwrap();
+    let bearer = |body: &Value| Header::new("Authorization", format!("Bearer {}", body["token"].as_str().unwrap()));
+    let response = client.post("/api/v2/turn_games").dispatch();
+    let location = response.headers().get_one("Location").unwrap().to_string();
+    let first: Value = response.into_json().unwrap();
+    let second: Value = client.post(format!("{}/players", location)).dispatch().into_json().unwrap();
+
+    // The second player starts a new game with the old token, only the second player leaves the turn-based game
+    let response = client.post("/api/v2/games").header(bearer(&second)).dispatch();
+    assert_eq!(response.status(), Status::Created);
+    let view: Value = client.get(location.as_str()).header(bearer(&first)).dispatch().into_json().unwrap();
+    assert_eq!(view["turn"]["players"], 1);
+    assert_eq!(client.get(location.as_str()).header(bearer(&second)).dispatch().status(), Status::Forbidden);
+
+    // The game is deleted once the last player leaves it
+    client.post("/api/v2/games").header(bearer(&first)).dispatch();
+    assert_eq!(client.post(format!("{}/players", location)).dispatch().status(), Status::NotFound);
+}
+
+#[test]
+fn test_games_per_client_limit() {
+    let client = Client::untracked(build_rocket_with_words(words("limit"))).unwrap();
+    let address = SocketAddr::from(([10, 0, 0, 1], 8000));
+    let bearer = |body: &Value| Header::new("Authorization", format!("Bearer {}", body["token"].as_str().unwrap()));
+    let response = client.post("/api/v2/races").remote(address).dispatch();
+    let location = response.headers().get_one("Location").unwrap().to_string();
+    let host: Value = response.into_json().unwrap();
+    let mut player = Value::Null;
+    for _ in 0..3 {
+        let response = client.post("/api/v2/games").remote(address).dispatch();
+        assert_eq!(response.status(), Status::Created);
+        player = response.into_json().unwrap();
+    }
+    // The game of the bot is the fifth game of the client
+    let response = client.post(format!("{}/bots", location)).remote(address).header(bearer(&host)).dispatch();
+    assert_eq!(response.status(), Status::Created);
+
+    // Further games are rejected and the running games are kept
+    let response = client.post("/api/v2/games").remote(address).dispatch();
+    assert_eq!(response.status(), Status::TooManyRequests);
+    assert_eq!(response.headers().get_one("Retry-After"), Some("60"));
+    let response = client.post("/api/register").remote(address).dispatch();
+    assert_eq!(response.status(), Status::TooManyRequests);
+    assert_eq!(response.headers().get_one("Retry-After"), Some("60"));
+    let response = client.post(format!("{}/bots", location)).remote(address).header(bearer(&host)).dispatch();
+    assert_eq!(response.status(), Status::TooManyRequests);
+    assert_eq!(response.headers().get_one("Retry-After"), Some("60"));
+    assert_eq!(client.get(location.as_str()).header(bearer(&host)).dispatch().status(), Status::Ok);
+
+    // Leaving the old game frees its slot for the race
+    let response = client.post(format!("{}/players", location)).remote(address).header(bearer(&player)).dispatch();
+    assert_eq!(response.status(), Status::Created);
+
+    // Other clients are not affected
+    let other = SocketAddr::from(([10, 0, 0, 2], 8000));
+    assert_eq!(client.post("/api/v2/games").remote(other).dispatch().status(), Status::Created);
+}
+
+#[test]
+fn test_spectate() {
+    let client = Client::untracked(build_rocket_with_words(words("spectate"))).unwrap();
+    let bearer = |token: &str| Header::new("Authorization", format!("Bearer {}", token));
+    let player: Value = client.post("/api/v2/games").dispatch().into_json().unwrap();
+    let token = player["token"].as_str().unwrap();
+    let game = format!("/api/v2/games/{}", player["game_id"].as_str().unwrap());
+    assert_eq!(player["spectators"], 0);
+    assert_eq!(player["spectating"], true);
+
+    let response = client.post(format!("{}/spectators", game)).header(bearer(token)).dispatch();
+    assert_eq!(response.status(), Status::Created);
+    let link: Value = response.into_json().unwrap();
+    let url = link["url"].as_str().unwrap().to_string();
+    client.post(format!("{}/guesses", game)).header(bearer(token)).json(&json!({ "guess": "U" })).dispatch();
+    client.post(format!("{}/guesses", game)).header(bearer(token)).json(&json!({ "guess": "Katze" })).dispatch();
+
+    let response = client.get(url.as_str()).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let view: Value = response.into_json().unwrap();
+    assert_eq!(view["game_string"], "_ U _ _");
+    assert_eq!(view["lives"], 6);
+    assert_eq!(view["guesses"], json!(["U", "KATZE"]));
+    assert_eq!(view["word"], Value::Null);
+    let view: Value = client.get(game.as_str()).header(bearer(token)).dispatch().into_json().unwrap();
+    assert_eq!(view["spectators"], 1);
+
+    // The spectator token can not be used to play and the player token is no spectator token
+    let spectator_token = link["token"].as_str().unwrap();
+    let response = client.post(format!("{}/guesses", game)).header(bearer(spectator_token)).json(&json!({ "guess": "H" })).dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+    assert_eq!(client.get(format!("/api/v2/spectate?token={}", token)).dispatch().status(), Status::Forbidden);
+    assert_eq!(client.get("/api/v2/spectate").dispatch().status(), Status::Forbidden);
+
+    let view: Value = client.put(format!("{}/spectating", game)).header(bearer(token)).json(&json!({ "enabled": false })).dispatch().into_json().unwrap();
+    assert_eq!(view["spectating"], false);
+    assert_eq!(view["spectators"], 0);
+    assert_eq!(client.get(url.as_str()).dispatch().status(), Status::Forbidden);
+    assert_eq!(client.post(format!("{}/spectators", game)).header(bearer(token)).dispatch().status(), Status::Conflict);
+
+    // Only the host can change the setting
+    let host: Value = client.post("/api/v2/turn_games").dispatch().into_json().unwrap();
+    let location = format!("/api/v2/games/{}", host["game_id"].as_str().unwrap());
+    let guest: Value = client.post(format!("{}/players", location)).dispatch().into_json().unwrap();
+    let response = client.put(format!("{}/spectating", location)).header(bearer(guest["token"].as_str().unwrap())).json(&json!({ "enabled": false })).dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+}
+
+#[test]
+fn test_chat() {
+    let client = Client::untracked(build_rocket_with_words(words("chat"))).unwrap();
+    let bearer = |body: &Value| Header::new("Authorization", format!("Bearer {}", body["token"].as_str().unwrap()));
+    let host: Value = client.post("/api/v2/turn_games").json(&json!({ "turn_timeout": 0 })).dispatch().into_json().unwrap();
+    let location = format!("/api/v2/games/{}", host["game_id"].as_str().unwrap());
+    let guest: Value = client.post(format!("{}/players", location)).dispatch().into_json().unwrap();
+
+    let response = client.post(format!("{}/chat", location)).header(bearer(&guest)).json(&json!({ "text": " Hallo! " })).dispatch();
+    assert_eq!(response.status(), Status::Created);
+    let message: Value = response.into_json().unwrap();
+    assert_eq!(message["player"], 2);
+    assert_eq!(message["text"], "Hallo!");
+    for text in ["  ", &"a".repeat(201)] {
+        let response = client.post(format!("{}/chat", location)).header(bearer(&host)).json(&json!({ "text": text })).dispatch();
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+
+    // The chooser knows the word, so it can not be sent while the round is running
+    client.put(format!("{}/word", location)).header(bearer(&host)).json(&json!({ "word": "Katze" })).dispatch();
+    let response = client.post(format!("{}/chat", location)).header(bearer(&host)).json(&json!({ "text": "k a t z e" })).dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    client.post(format!("{}/guesses", location)).header(bearer(&guest)).json(&json!({ "guess": "Katze" })).dispatch();
+    let response = client.post(format!("{}/chat", location)).header(bearer(&host)).json(&json!({ "text": "It was Katze" })).dispatch();
+    assert_eq!(response.status(), Status::Created);
+
+    let chat: Value = client.get(format!("{}/chat", location)).header(bearer(&guest)).dispatch().into_json().unwrap();
+    let texts: Vec<&str> = chat.as_array().unwrap().iter().map(|message| message["text"].as_str().unwrap()).collect();
+    assert_eq!(texts, ["Hallo!", "It was Katze"]);
+    let other: Value = client.post("/api/v2/games").dispatch().into_json().unwrap();
+    assert_eq!(client.get(format!("{}/chat", location)).header(bearer(&other)).dispatch().status(), Status::Forbidden);
+
+    // The word is filtered in every running round, not only in turn-based games
+    let game = format!("/api/v2/games/{}", other["game_id"].as_str().unwrap());
+    let response = client.post(format!("{}/chat", game)).header(bearer(&other)).json(&json!({ "text": "Hund?" })).dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    let message: Value = client.post(format!("{}/chat", game)).header(bearer(&other)).json(&json!({ "text": "Hallo" })).dispatch().into_json().unwrap();
+    assert_eq!(message["player"], 1);
+    assert_eq!(client.get(format!("{}/events", location)).header(bearer(&other)).dispatch().status(), Status::Forbidden);
+}
+
+#[test]
+fn test_rematch() {
+    let client = client("rematch");
+    client.post("/api/register").dispatch();
+    let game_id = text(client.get("/api/game_id").dispatch());
+    assert_eq!(client.post("/api/rematch").dispatch().status(), Status::Conflict);
+    assert_eq!(text(guess(&client, "Hund")), "1");
+    let response = client.post("/api/rematch").json(&json!({ "best_of": 3 })).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.headers().get_one("Deprecation"), None);
+    assert_eq!(response.into_json::<Value>().unwrap(), json!({ "best_of": 3, "rounds": 0, "won": 0, "lost": 0, "finished": false }));
+    assert_eq!(text(client.get("/api/game_id").dispatch()), game_id);
+    assert_eq!(text(client.get("/api/registered").dispatch()), "playing");
+    assert_eq!(text(client.get("/api/game_string").dispatch()), "_ _ _ _");
+
+    assert_eq!(text(guess(&client, "Hund")), "1");
+    let game = format!("/api/v2/games/{}", game_id);
+    let view: Value = client.post(format!("{}/rematch", game)).dispatch().into_json().unwrap();
+    assert_eq!(view["status"], "playing");
+    assert_eq!(view["lives"], 7);
+    assert_eq!(view["series"], json!({ "best_of": 3, "rounds": 1, "won": 1, "lost": 0, "finished": false }));
+    for letter in ["A", "B", "C", "E", "F", "G", "I"] {
+        guess(&client, letter);
+    }
+    let view: Value = client.get(game.as_str()).dispatch().into_json().unwrap();
+    assert_eq!(view["series"], json!({ "best_of": 3, "rounds": 2, "won": 1, "lost": 1, "finished": false }));
+    let response = client.post(format!("{}/rematch", game)).json(&json!({ "best_of": 100 })).dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+#[test]
+fn test_time_limits() {
+    let client = Client::untracked(build_rocket_with_words(words("time"))).unwrap();
+    let bearer = |body: &Value| Header::new("Authorization", format!("Bearer {}", body["token"].as_str().unwrap()));
+    let response = client.post("/api/v2/games").json(&json!({ "guess_time_limit": 7200 })).dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    let timed: Value = client.post("/api/v2/games").json(&json!({ "guess_time_limit": 1, "game_time_limit": 60 })).dispatch().into_json().unwrap();
+    assert_eq!(timed["time"], json!({ "guess_seconds_left": 0, "game_seconds_left": 59, "score": null }));
+
+    let response = client.post("/api/v2/time_attack").json(&json!({ "duration": 1 })).dispatch();
+    assert_eq!(response.status(), Status::Created);
+    let attack: Value = response.into_json().unwrap();
+    let game = format!("/api/v2/games/{}", attack["game_id"].as_str().unwrap());
+    let body: Value = client.post(format!("{}/guesses", game)).header(bearer(&attack)).json(&json!({ "guess": "Hund" })).dispatch().into_json().unwrap();
+    assert_eq!(body["result"], 1);
+    assert_eq!(body["game"]["status"], "playing");
+    assert_eq!(body["game"]["game_string"], "_ _ _ _");
+    assert_eq!(body["game"]["time"]["score"], 1);
+
+    std::thread::sleep(std::time::Duration::from_millis(1100));
+    let view: Value = client.get(format!("/api/v2/games/{}", timed["game_id"].as_str().unwrap())).header(bearer(&timed)).dispatch().into_json().unwrap();
+    assert_eq!(view["lives"], 6);
+    let view: Value = client.get(game.as_str()).header(bearer(&attack)).dispatch().into_json().unwrap();
+    assert_eq!(view["status"], "won");
+    assert_eq!(view["time"]["game_seconds_left"], 0);
+    assert_eq!(client.post("/api/v2/time_attack").json(&json!({ "duration": 0 })).dispatch().status(), Status::BadRequest);
+}
+
+#[test]
+fn test_evil_game() {
+    let client = client("evil");
+    let response = client.post("/api/v2/evil_games").dispatch();
+    assert_eq!(response.status(), Status::Created);
+    let body: Value = response.into_json().unwrap();
+    assert_eq!(body["evil"], true);
+    assert_eq!(body["game_string"], "_ _ _ _");
+    let game = format!("/api/v2/games/{}", body["game_id"].as_str().unwrap());
+    // The words file only contains one word, so the evil game can not evade the guesses
+    let body: Value = client.post(format!("{}/guesses", game)).json(&json!({ "guess": "Hund" })).dispatch().into_json().unwrap();
+    assert_eq!(body["result"], 1);
+    let view: Value = client.post(format!("{}/rematch", game)).dispatch().into_json().unwrap();
+    assert_eq!(view["evil"], true);
+    assert_eq!(view["status"], "playing");
+    assert_eq!(text(guess(&client, "U")), "2");
+}
+
+#[test]
+fn test_solver() {
+    let client = client("solver");
+    let game: Value = client.post("/api/v2/games").dispatch().into_json().unwrap();
+    let game = format!("/api/v2/games/{}", game["game_id"].as_str().unwrap());
+    // Only one word is known, so the solver suggests it right away
+    let hint: Value = client.get(format!("{}/hint", game)).dispatch().into_json().unwrap();
+    assert_eq!(hint["guess"], WORD);
+    assert_eq!(hint["candidates"], 1);
+    assert_eq!(hint["letters"].as_array().unwrap().len(), 5);
+    assert_eq!(client.post(format!("{}/bots", game)).dispatch().status(), Status::Conflict);
+    client.post(format!("{}/guesses", game)).json(&json!({ "guess": "Hund" })).dispatch();
+    assert_eq!(client.get(format!("{}/hint", game)).dispatch().status(), Status::Conflict);
+
+    let race: Value = client.post("/api/v2/races").dispatch().into_json().unwrap();
+    let race = format!("/api/v2/races/{}", race["race_id"].as_str().unwrap());
+    assert_eq!(client.post(format!("{}/bots", race)).json(&json!({ "strength": 11 })).dispatch().status(), Status::BadRequest);
+    let response = client.post(format!("{}/bots", race)).json(&json!({ "strength": 10 })).dispatch();
+    assert_eq!(response.status(), Status::Created);
+    let body: Value = response.into_json().unwrap();
+    assert_eq!(body["players"].as_array().unwrap().len(), 2);
+
+    let turn_game: Value = client.post("/api/v2/turn_games").dispatch().into_json().unwrap();
+    let turn_game = format!("/api/v2/games/{}", turn_game["game_id"].as_str().unwrap());
+    assert_eq!(client.post(format!("{}/bots", race)).dispatch().status(), Status::Forbidden);
+    let response = client.post(format!("{}/bots", turn_game)).dispatch();
+    assert_eq!(response.status(), Status::Created);
+    let body: Value = response.into_json().unwrap();
+    assert_eq!(body["turn"]["players"], 2);
+    let body: Value = client.put(format!("{}/word", turn_game)).json(&json!({ "word": "Katze" })).dispatch().into_json().unwrap();
+    assert_eq!(body["turn"]["current"], 2);
+}
@@ -0,0 +1,120 @@
+//! Properties of the game engine that have to hold for all words and guess sequences
+
+use hangman_rs_web::game::{base_game::Game, guess::Guess, ALPHABET, MAX_LIVES};
+use proptest::prelude::*;
+use uuid::Uuid;
+
+/// Words like they are contained in a normalized words file
+fn word() -> impl Strategy<Value = String> {
+    "[A-Z]{1,12}(-[A-Z]{1,6})?"
+}
+
+/// Letters and words, the words are likely to be wrong but may also hit the solution
+fn guesses() -> impl Strategy<Value = Vec<Guess>> {
+    let letter = proptest::sample::select(ALPHABET.chars().collect::<Vec<char>>()).prop_map(Guess::Letter);
+    let word = word().prop_map(Guess::Word);
+    proptest::collection::vec(prop_oneof![4 => letter, 1 => word], 0..60)
+}
+
+/// Guesses with few letters, so that the candidates of an evil game are split into several families
+fn evil_guesses() -> impl Strategy<Value = Vec<Guess>> {
+    let letter = proptest::sample::select(vec!['A', 'B', 'C', 'D', 'E', 'F', 'G']).prop_map(Guess::Letter);
+    let word = "[A-F]{5}".prop_map(Guess::Word);
+    proptest::collection::vec(prop_oneof![4 => letter, 1 => word], 0..20)
+}
+
+fn new_game(word: &str) -> Game {
+    Game::with_word(Uuid::new_v4(), Uuid::new_v4(), word, MAX_LIVES)
+}
+
+proptest! {
+    #[test]
+    fn lives_stay_in_range(word in word(), guesses in guesses()) {
+        let mut game = new_game(&word);
+        for guess in guesses {
+            let lives = game.lives();
+            let result = game.guess(guess);
+            prop_assert!((0..=MAX_LIVES).contains(&game.lives()));
+            match result {
+                3 | 4 => prop_assert_eq!(game.lives(), lives - 1),
+                _ => prop_assert_eq!(game.lives(), lives),
+            }
+            prop_assert_eq!(result == 4, game.lives() == 0 && lives == 1);
+        }
+    }
+
+    #[test]
+    fn game_string_matches_word(word in word(), guesses in guesses()) {
+        let mut game = new_game(&word);
+        for guess in guesses {
+            game.guess(guess);
+            let game_string = game.game_string();
+            prop_assert_eq!(game_string.split(' ').count(), word.chars().count());
+            for (shown, letter) in game_string.split(' ').zip(word.chars()) {
+                prop_assert!(shown == "_" || shown == letter.to_string());
+            }
+            match game.completed() {
+                Some(true) => {
+                    prop_assert!(!game_string.contains('_'));
+                    prop_assert_eq!(game.word(), Some(word.clone()));
+                },
+                Some(false) => prop_assert_eq!(game.word(), Some(word.clone())),
+                None => prop_assert_eq!(game.word(), None),
+            }
+        }
+    }
+
+    #[test]
+    fn repeated_guesses_are_free(word in word(), guesses in guesses()) {
+        let mut game = new_game(&word);
+        for guess in guesses {
+            let first = game.guess(guess.clone());
+            if let Guess::Letter(c) = guess {
+                let lives = game.lives();
+                let guessed_letters = game.guessed_letters();
+                prop_assert!(guessed_letters.contains(c) || first == 6);
+                prop_assert!(matches!(game.guess(guess), 5 | 6));
+                prop_assert_eq!(game.lives(), lives);
+                prop_assert_eq!(game.guessed_letters(), guessed_letters);
+            }
+        }
+    }
+
+    #[test]
+    fn ended_games_do_not_change(word in word(), guesses in guesses(), extra in guesses()) {
+        let mut game = new_game(&word);
+        for guess in guesses {
+            game.guess(guess);
+        }
+        game.force_end();
+        let (lives, game_string, status) = (game.lives(), game.game_string(), game.status());
+        for guess in extra {
+            prop_assert_eq!(game.guess(guess), 6);
+        }
+        prop_assert_eq!((game.lives(), game.game_string(), game.status()), (lives, game_string, status));
+    }
+
+    #[test]
+    fn evil_game_matches_final_word(candidates in proptest::collection::vec("[A-F]{5}", 1..30), guesses in evil_guesses()) {
+        let mut evil = Game::evil(Uuid::new_v4(), Uuid::new_v4(), &candidates[0], candidates.clone());
+        let results: Vec<(i32, String)> = guesses.iter().map(|guess| (evil.guess(guess.clone()), evil.game_string())).collect();
+        // Replaying the guesses with the word that is left in the end gives the same results
+        let solution = evil.solution();
+        prop_assert!(candidates.contains(&solution));
+        let mut game = new_game(&solution);
+        for (guess, (result, game_string)) in guesses.into_iter().zip(results) {
+            prop_assert_eq!(game.guess(guess), result);
+            prop_assert_eq!(game.game_string(), game_string);
+        }
+        prop_assert_eq!((game.lives(), game.status()), (evil.lives(), evil.status()));
+    }
+
+    #[test]
+    fn parse_never_panics(input in any::<String>()) {
+        match input.parse::<Guess>() {
+            Ok(Guess::Letter(c)) => prop_assert!(ALPHABET.contains(c)),
+            Ok(Guess::Word(word)) => prop_assert!(word.chars().all(|c| ALPHABET.contains(c) || c == '-')),
+            Err(_) => {},
+        }
+    }
+}
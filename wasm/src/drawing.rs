@@ -0,0 +1,296 @@
+use std::{cell::RefCell, f64::consts::PI, rc::Rc};
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+
+/// How long the animation that is played when a life is lost takes in milliseconds
+const ANIMATION_DURATION: f64 = 600.0;
+
+/// All parts of the drawing in the order in which they are drawn.
+///
+/// Coordinates are relative to the canvas size, `0.0` is the top/left border and `1.0` the bottom/right border.
+const SEGMENTS: [Segment; 11] = [
+    // Gallows
+    Segment::Line(0.1, 0.95, 0.6, 0.95),
+    Segment::Line(0.25, 0.95, 0.25, 0.05),
+    Segment::Line(0.25, 0.05, 0.7, 0.05),
+    Segment::Line(0.25, 0.2, 0.4, 0.05),
+    Segment::Line(0.7, 0.05, 0.7, 0.18),
+    // Figure
+    Segment::Circle(0.7, 0.26, 0.08),
+    Segment::Line(0.7, 0.34, 0.7, 0.62),
+    Segment::Line(0.7, 0.4, 0.58, 0.52),
+    Segment::Line(0.7, 0.4, 0.82, 0.52),
+    Segment::Line(0.7, 0.62, 0.6, 0.8),
+    Segment::Line(0.7, 0.62, 0.8, 0.8),
+];
+
+/// Index of the first segment that belongs to the figure and not to the gallows
+const FIRST_FIGURE_SEGMENT: usize = 5;
+
+/// Callback that is invoked by `requestAnimationFrame`, shared so that it can schedule itself again
+type FrameCallback = Rc<RefCell<Option<Closure<dyn FnMut(f64)>>>>;
+
+/// A single part of the drawing
+enum Segment {
+    /// Line from (`x1`, `y1`) to (`x2`, `y2`)
+    Line(f64, f64, f64, f64),
+    /// Circle with the center (`x`, `y`) and the radius `r`
+    Circle(f64, f64, f64),
+}
+
+/// The colors that are used to draw the hangman
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Theme {
+    /// Color with which the canvas is cleared
+    background: &'static str,
+    /// Color of the gallows
+    gallows: &'static str,
+    /// Color of the figure
+    figure: &'static str,
+    /// Line width relative to the canvas width
+    line_width: f64,
+}
+
+impl Theme {
+    /// Light theme with black lines on white background
+    const LIGHT: Theme = Theme {
+        background: "#ffffff",
+        gallows: "#5c4033",
+        figure: "#000000",
+        line_width: 0.02,
+    };
+
+    /// Dark theme that matches the rest of the page
+    const DARK: Theme = Theme {
+        background: "#1b1b1b",
+        gallows: "#a0a0a0",
+        figure: "#ffffff",
+        line_width: 0.02,
+    };
+
+    /// Chalk on a blackboard
+    const CHALKBOARD: Theme = Theme {
+        background: "#2f4f3f",
+        gallows: "#e8e4d8",
+        figure: "#f4f1e8",
+        line_width: 0.03,
+    };
+
+    /// Returns the theme with the name.
+    ///
+    /// Possible values: `light`, `dark`, `chalkboard`. Unknown names fall back to `dark`.
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "light" => Self::LIGHT,
+            "chalkboard" => Self::CHALKBOARD,
+            _ => Self::DARK,
+        }
+    }
+}
+
+/// Calculates how many segments of the drawing should be visible.
+///
+/// The whole drawing is spread over the number of lives so that it is complete when no lives are left, independent
+/// of how many lives a game has. The fractional part is the progress of the segment that is only partially drawn.
+pub fn visible_segments(lives_left: i32, max_lives: i32) -> f64 {
+    if max_lives <= 0 {
+        return SEGMENTS.len() as f64;
+    }
+    let mistakes = (max_lives - lives_left.clamp(0, max_lives)) as f64;
+    mistakes / max_lives as f64 * SEGMENTS.len() as f64
+}
+
+/// Renders the hangman on a canvas.
+///
+/// The drawing progresses each time a life is lost, see [visible_segments].
+#[wasm_bindgen]
+pub struct HangmanCanvas {
+    state: Rc<RefCell<CanvasState>>,
+}
+
+/// State of a [HangmanCanvas] that is shared with the running animation
+struct CanvasState {
+    context: CanvasRenderingContext2d,
+    width: f64,
+    height: f64,
+    theme: Theme,
+    max_lives: i32,
+    /// The amount of segments that is currently visible
+    shown: f64,
+    /// Increases each time an animation is started, used to stop outdated animations
+    animation_id: u32,
+}
+
+#[wasm_bindgen]
+impl HangmanCanvas {
+    /// Creates a renderer for the canvas with the id `canvas_id`.
+    ///
+    /// # Arguments
+    /// - `max_lives` The number of lives a game starts with
+    /// - `theme` The name of the theme, see [Theme::from_name]
+    #[wasm_bindgen(constructor)]
+    pub fn new(canvas_id: &str, max_lives: i32, theme: &str) -> Result<HangmanCanvas, JsValue> {
+        let canvas = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.get_element_by_id(canvas_id))
+            .ok_or_else(|| JsValue::from_str(&format!("Canvas with id {} not found", canvas_id)))?
+            .dyn_into::<HtmlCanvasElement>()?;
+        let context = canvas
+            .get_context("2d")?
+            .ok_or_else(|| JsValue::from_str("Canvas does not support 2d rendering"))?
+            .dyn_into::<CanvasRenderingContext2d>()?;
+        let state = CanvasState {
+            context,
+            width: canvas.width() as f64,
+            height: canvas.height() as f64,
+            theme: Theme::from_name(theme),
+            max_lives,
+            shown: 0.0,
+            animation_id: 0,
+        };
+        state.render();
+        Ok(HangmanCanvas {
+            state: Rc::new(RefCell::new(state)),
+        })
+    }
+
+    /// Draws the hangman for the amount of lives left without playing an animation.
+    pub fn draw(&self, lives_left: i32) {
+        let mut state = self.state.borrow_mut();
+        state.animation_id += 1;
+        state.shown = visible_segments(lives_left, state.max_lives);
+        state.render();
+    }
+
+    /// Updates the drawing to the amount of lives left.
+    ///
+    /// When lives have been lost since the last update the new parts are animated.
+    pub fn update(&self, lives_left: i32) -> Result<(), JsValue> {
+        let (from, to, animation_id) = {
+            let mut state = self.state.borrow_mut();
+            let target = visible_segments(lives_left, state.max_lives);
+            if target <= state.shown {
+                drop(state);
+                self.draw(lives_left);
+                return Ok(());
+            }
+            state.animation_id += 1;
+            (state.shown, target, state.animation_id)
+        };
+        animate(Rc::clone(&self.state), from, to, animation_id)
+    }
+
+    /// Changes the theme and redraws the hangman.
+    pub fn set_theme(&self, theme: &str) {
+        let mut state = self.state.borrow_mut();
+        state.theme = Theme::from_name(theme);
+        state.render();
+    }
+}
+
+impl CanvasState {
+    /// Clears the canvas and draws all segments that are currently visible
+    fn render(&self) {
+        let ctx = &self.context;
+        ctx.set_fill_style(&JsValue::from_str(self.theme.background));
+        ctx.fill_rect(0.0, 0.0, self.width, self.height);
+        ctx.set_line_width(self.theme.line_width * self.width);
+        ctx.set_line_cap("round");
+        for (index, segment) in SEGMENTS.iter().enumerate() {
+            let progress = (self.shown - index as f64).clamp(0.0, 1.0);
+            if progress <= 0.0 {
+                break;
+            }
+            let color = match index < FIRST_FIGURE_SEGMENT {
+                true => self.theme.gallows,
+                false => self.theme.figure,
+            };
+            ctx.set_stroke_style(&JsValue::from_str(color));
+            ctx.begin_path();
+            match *segment {
+                Segment::Line(x1, y1, x2, y2) => {
+                    ctx.move_to(x1 * self.width, y1 * self.height);
+                    ctx.line_to((x1 + (x2 - x1) * progress) * self.width, (y1 + (y2 - y1) * progress) * self.height);
+                }
+                Segment::Circle(x, y, r) => {
+                    // Start at the top of the circle so that it connects to the rope
+                    let _ = ctx.arc(x * self.width, y * self.height, r * self.width, -PI / 2.0, -PI / 2.0 + 2.0 * PI * progress);
+                }
+            }
+            ctx.stroke();
+        }
+    }
+}
+
+/// Animates the drawing from `from` to `to` visible segments.
+///
+/// The animation stops early when another animation is started in the meantime.
+fn animate(state: Rc<RefCell<CanvasState>>, from: f64, to: f64, animation_id: u32) -> Result<(), JsValue> {
+    let frame: FrameCallback = Rc::new(RefCell::new(None));
+    let next_frame = Rc::clone(&frame);
+    let mut start = None;
+    *frame.borrow_mut() = Some(Closure::wrap(Box::new(move |timestamp: f64| {
+        let start = *start.get_or_insert(timestamp);
+        let progress = ((timestamp - start) / ANIMATION_DURATION).min(1.0);
+        {
+            let mut state = state.borrow_mut();
+            if state.animation_id != animation_id {
+                next_frame.borrow_mut().take();
+                return;
+            }
+            // Ease out so that the line slows down towards its end
+            state.shown = from + (to - from) * (1.0 - (1.0 - progress).powi(3));
+            state.render();
+        }
+        if progress < 1.0 {
+            if let Some(closure) = next_frame.borrow().as_ref() {
+                let _ = request_animation_frame(closure);
+            }
+        } else {
+            // Drop the closure to break the reference cycle
+            next_frame.borrow_mut().take();
+        }
+    }) as Box<dyn FnMut(f64)>));
+    request_animation_frame(frame.borrow().as_ref().unwrap())?;
+    Ok(())
+}
+
+/// Schedules the closure to be called before the next repaint
+fn request_animation_frame(closure: &Closure<dyn FnMut(f64)>) -> Result<i32, JsValue> {
+    web_sys::window()
+        .ok_or_else(|| JsValue::from_str("No window available"))?
+        .request_animation_frame(closure.as_ref().unchecked_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{visible_segments, Theme, SEGMENTS};
+
+    #[test]
+    fn test_visible_segments_bounds() {
+        assert_eq!(visible_segments(7, 7), 0.0);
+        assert_eq!(visible_segments(0, 7), SEGMENTS.len() as f64);
+        assert_eq!(visible_segments(-3, 7), SEGMENTS.len() as f64);
+    }
+
+    #[test]
+    fn test_visible_segments_any_lives() {
+        for max_lives in 1..=20 {
+            let mut last = -1.0;
+            for lives_left in (0..=max_lives).rev() {
+                let shown = visible_segments(lives_left, max_lives);
+                assert!(shown > last);
+                last = shown;
+            }
+            assert_eq!(last, SEGMENTS.len() as f64);
+        }
+    }
+
+    #[test]
+    fn test_theme_fallback() {
+        assert_eq!(Theme::from_name("unknown"), Theme::DARK);
+        assert_eq!(Theme::from_name("light"), Theme::LIGHT);
+    }
+}
@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 extern crate console_error_panic_hook;
 
+mod drawing;
 mod singleplayer;
 mod utils;
 
@@ -1,9 +1,270 @@
-use web_sys::console;
+use std::{cell::Cell, rc::Rc};
+
 use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{console, Event, HtmlButtonElement, HtmlElement, HtmlInputElement, HtmlSelectElement, KeyboardEvent};
+
+use crate::{drawing::HangmanCanvas, utils::{element_by_id, get_text, post_request, post_request_data, set_hidden, show_text}};
+
+/// Key under which the selected theme is stored in the local storage
+const THEME_KEY: &str = "theme";
+
+/// State of the singleplayer page
+struct Page {
+    /// Draws the hangman
+    canvas: HangmanCanvas,
+    /// Set when the game has ended, no more guesses are sent afterwards
+    game_over: Cell<bool>,
+}
 
-/// Initialize the main lobby state
+/// Initializes the singleplayer page.
+///
+/// This is the only function that has to be called by JavaScript, all other page logic is done by the wasm code.
 #[wasm_bindgen]
-pub extern fn init() {
+pub extern "C" fn init() {
     console_error_panic_hook::set_once();
-    console::log_1(&"Hello from Rust!".into());
+    spawn_local(async {
+        if let Err(err) = prepare_page().await {
+            console::error_2(&"Unable to prepare page:".into(), &err);
+        }
+    });
+}
+
+/// Prepares the singleplayer page by doing the following:
+/// - Check if the player is registered and restore the page state if the player is playing a game
+/// - Register new user session when the player is not registered
+/// - Build the on-screen keyboard and set all event listeners
+async fn prepare_page() -> Result<(), JsValue> {
+    let status = get_text("api/registered").await?;
+    if status == "false" {
+        register().await?;
+    }
+    let max_lives = get_text("api/max_lives").await?.parse::<i32>()
+        .map_err(|_err| JsValue::from_str("Invalid number of lives"))?;
+    let theme = stored_theme();
+    element_by_id::<HtmlSelectElement>("theme-select")?.set_value(&theme);
+    let page = Rc::new(Page {
+        canvas: HangmanCanvas::new("image", max_lives, &theme)?,
+        game_over: Cell::new(false),
+    });
+    build_keyboard(&page)?;
+    update_page(&page, false).await?;
+    match status.as_str() {
+        "won" => game_end(&page, true).await?,
+        "lost" => game_end(&page, false).await?,
+        _ => (),
+    }
+    set_event_listeners(&page)
+}
+
+/// Registers with the server, this will always set a new uuid
+async fn register() -> Result<(), JsValue> {
+    let uuid = post_request("api/register".to_string()).await?;
+    console::log_2(&"uuid:".into(), &uuid);
+    Ok(())
+}
+
+/// Updates all page elements and hides the placeholders.
+///
+/// When `animate` is set, lives that have been lost since the last update are animated in the drawing.
+async fn update_page(page: &Page, animate: bool) -> Result<(), JsValue> {
+    show_text("word", &get_text("api/game_string").await?)?;
+    set_hidden("word-placeholder", true)?;
+
+    let guessed_letters = get_text("api/guessed_letters").await?;
+    show_text("guessed-letters", &guessed_letters)?;
+    set_hidden("guessed-letters-placeholder", true)?;
+    update_keyboard(&guessed_letters)?;
+
+    let lives = get_text("api/lives").await?;
+    show_text("lives-left", &lives)?;
+    set_hidden("lives-left-placeholder", true)?;
+    let lives = lives.parse::<i32>().unwrap_or(0);
+    match animate {
+        true => page.canvas.update(lives)?,
+        false => page.canvas.draw(lives),
+    }
+    set_hidden("image", false)?;
+    set_hidden("image-placeholder", true)
+}
+
+/// Sends the guess to the server and updates the page depending on the result.
+///
+/// See [Game::guess](../../hangman_rs_web/game/base_game/struct.Game.html#method.guess) for the meaning of the result.
+async fn guess(page: &Page, guess: String) -> Result<(), JsValue> {
+    if page.game_over.get() || guess.trim().is_empty() {
+        return Ok(());
+    }
+    let response = post_request_data("api/guess".to_string(), JsValue::from_str(guess.trim())).await?;
+    element_by_id::<HtmlInputElement>("user-input")?.set_value("");
+    match response.as_f64().map(|result| result as i32) {
+        Some(1) => {
+            update_page(page, true).await?;
+            game_end(page, true).await?;
+        },
+        Some(2) | Some(3) => update_page(page, true).await?,
+        Some(4) => {
+            update_page(page, true).await?;
+            game_end(page, false).await?;
+        },
+        Some(5) => window()?.alert_with_message("This character was already submitted")?,
+        Some(6) => window()?.alert_with_message("The game has already ended")?,
+        // The guess was rejected by the server, the response contains the reason
+        None if response.is_string() => window()?.alert_with_message(&response.as_string().unwrap_or_default())?,
+        _ => console::warn_2(&"Unexpected guess response:".into(), &response),
+    }
+    Ok(())
+}
+
+/// Shows the game over message and hides the input.
+///
+/// When the game was lost the correct word is shown.
+async fn game_end(page: &Page, won: bool) -> Result<(), JsValue> {
+    page.game_over.set(true);
+    set_hidden("input-container", true)?;
+    set_hidden("keyboard", true)?;
+    set_hidden("game-won-container", !won)?;
+    set_hidden("game-lost-container", won)?;
+    if !won {
+        show_text("word", &get_text("api/word").await?)?;
+    }
+    set_hidden("game-over-container", false)
+}
+
+/// Starts a new round with a new word in the same game and reloads the page.
+///
+/// The server only starts a rematch once the round is over, a running game is deleted instead and a new game is
+/// registered when the page is loaded again.
+async fn new_game() -> Result<(), JsValue> {
+    match get_text("api/registered").await?.as_str() {
+        "won" | "lost" => {
+            post_request("api/rematch".to_string()).await?;
+        },
+        _ => {
+            get_text("api/delete_game").await?;
+        },
+    }
+    window()?.location().set_href("/singleplayer")
+}
+
+/// Creates a button for each letter in the keyboard container.
+fn build_keyboard(page: &Rc<Page>) -> Result<(), JsValue> {
+    let keyboard = element_by_id::<HtmlElement>("keyboard")?;
+    let document = window()?.document().ok_or_else(|| JsValue::from_str("No document available"))?;
+    for letter in 'A'..='Z' {
+        let button = document.create_element("button")?.dyn_into::<HtmlButtonElement>()?;
+        button.set_id(&format!("key-{}", letter));
+        button.set_class_name("btn btn-outline-light btn-sm keyboard-key");
+        button.set_inner_text(&letter.to_string());
+        on_event(&button, "click", page, move |page, _event| {
+            spawn_local(async move {
+                log_error(guess(&page, letter.to_string()).await);
+            });
+        })?;
+        keyboard.append_child(&button)?;
+    }
+    Ok(())
+}
+
+/// Disables the keyboard buttons of all letters that have already been guessed.
+fn update_keyboard(guessed_letters: &str) -> Result<(), JsValue> {
+    for letter in 'A'..='Z' {
+        element_by_id::<HtmlButtonElement>(&format!("key-{}", letter))?.set_disabled(guessed_letters.contains(letter));
+    }
+    Ok(())
+}
+
+/// Sets all event listeners
+fn set_event_listeners(page: &Rc<Page>) -> Result<(), JsValue> {
+    let submit_button = element_by_id::<HtmlButtonElement>("button-submit")?;
+    on_event(&submit_button, "click", page, |page, _event| {
+        spawn_local(async move {
+            let input = element_by_id::<HtmlInputElement>("user-input").map(|input| input.value());
+            match input {
+                Ok(value) => log_error(guess(&page, value).await),
+                Err(err) => log_error(Err(err)),
+            }
+        });
+    })?;
+    let new_game_button = element_by_id::<HtmlButtonElement>("button-new-game")?;
+    on_event(&new_game_button, "click", page, |_page, _event| {
+        spawn_local(async { log_error(new_game().await) });
+    })?;
+    let theme_select = element_by_id::<HtmlSelectElement>("theme-select")?;
+    on_event(&theme_select, "change", page, |page, event| {
+        let Some(select) = event.target().and_then(|target| target.dyn_into::<HtmlSelectElement>().ok()) else {
+            return;
+        };
+        let theme = select.value();
+        if let Ok(Some(storage)) = window().and_then(|window| window.local_storage()) {
+            let _ = storage.set_item(THEME_KEY, &theme);
+        }
+        page.canvas.set_theme(&theme);
+    })?;
+    let document = window()?.document().ok_or_else(|| JsValue::from_str("No document available"))?;
+    on_event(&document, "keydown", page, |page, event| {
+        let Some(event) = event.dyn_ref::<KeyboardEvent>() else {
+            return;
+        };
+        let in_input = event.target()
+            .and_then(|target| target.dyn_into::<HtmlInputElement>().ok())
+            .is_some();
+        if in_input {
+            // Enter submits the content of the text field
+            if event.key() == "Enter" {
+                event.prevent_default();
+                if let Ok(button) = element_by_id::<HtmlButtonElement>("button-submit") {
+                    button.click();
+                }
+            }
+            return;
+        }
+        // Letters typed outside of the text field are guessed directly
+        let key = event.key();
+        let mut chars = key.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            if c.is_ascii_alphabetic() && !event.ctrl_key() && !event.alt_key() && !event.meta_key() {
+                spawn_local(async move {
+                    log_error(guess(&page, c.to_ascii_uppercase().to_string()).await);
+                });
+            }
+        }
+    })
+}
+
+/// Adds an event listener to the target that calls `handler` with the page state.
+///
+/// The listener is kept alive for the lifetime of the page.
+fn on_event<F>(target: &web_sys::EventTarget, event: &str, page: &Rc<Page>, handler: F) -> Result<(), JsValue>
+where
+    F: Fn(Rc<Page>, Event) + 'static,
+{
+    let page = Rc::clone(page);
+    let closure = Closure::wrap(Box::new(move |event: Event| handler(Rc::clone(&page), event)) as Box<dyn FnMut(Event)>);
+    target.add_event_listener_with_callback(event, closure.as_ref().unchecked_ref())?;
+    closure.forget();
+    Ok(())
+}
+
+/// Returns the theme that was selected the last time or `dark` when no theme was selected yet
+fn stored_theme() -> String {
+    window()
+        .and_then(|window| window.local_storage())
+        .ok()
+        .flatten()
+        .and_then(|storage| storage.get_item(THEME_KEY).ok().flatten())
+        .unwrap_or_else(|| String::from("dark"))
+}
+
+/// Returns the browser window
+fn window() -> Result<web_sys::Window, JsValue> {
+    web_sys::window().ok_or_else(|| JsValue::from_str("No window available"))
+}
+
+/// Logs the error to the console when the result is an error
+fn log_error(result: Result<(), JsValue>) {
+    if let Err(err) = result {
+        console::error_1(&err);
+    }
 }
@@ -1,7 +1,7 @@
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::JsFuture;
-use web_sys::{Request, RequestInit, RequestMode, Response};
+use web_sys::{HtmlElement, Request, RequestInit, RequestMode, Response};
 
 /// Sends a get request to the specified url.
 /// 
@@ -32,14 +32,42 @@ pub async fn post_request(url: String) -> Result<JsValue, JsValue> {
 
 /// Sends a POST request to url with the specified java script value as data field.
 /// 
-/// This function expects the response to be json.
+/// The data is serialized to json before it is sent. This function expects the response to be json.
 #[wasm_bindgen]
-#[allow(deprecated)]
-#[deprecated(note = "This function does not yet work properly, use `postData` located in `utils.js` instead.")]
 pub async fn post_request_data(url: String, data: JsValue) -> Result<JsValue, JsValue> {
     send_request(url, Method::Post, Some(&data), ResponseType::Json).await
 }
 
+/// Sends a get request to the specified url and returns the response as text.
+pub async fn get_text(url: &str) -> Result<String, JsValue> {
+    let response = send_request(url.to_string(), Method::Get, None, ResponseType::Text).await?;
+    response.as_string().ok_or_else(|| JsValue::from_str("Response is not a string"))
+}
+
+/// Returns the element with the id cast to the type `T`.
+pub fn element_by_id<T: JsCast>(id: &str) -> Result<T, JsValue> {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(id))
+        .ok_or_else(|| JsValue::from_str(&format!("Element with id {} not found", id)))?
+        .dyn_into::<T>()
+        .map_err(|_err| JsValue::from_str(&format!("Element with id {} has the wrong type", id)))
+}
+
+/// Sets the text of the element with the id and makes it visible.
+pub fn show_text(id: &str, text: &str) -> Result<(), JsValue> {
+    let element = element_by_id::<HtmlElement>(id)?;
+    element.set_inner_text(text);
+    element.set_hidden(false);
+    Ok(())
+}
+
+/// Hides or shows the element with the id.
+pub fn set_hidden(id: &str, hidden: bool) -> Result<(), JsValue> {
+    element_by_id::<HtmlElement>(id)?.set_hidden(hidden);
+    Ok(())
+}
+
 /// Different types of http requests
 enum Method {
     Post,
@@ -52,10 +80,9 @@ enum ResponseType {
     Json,
 }
 
-/// Sends a request to the url with the specified method. Returns the response as json.
+/// Sends a request to the url with the specified method. Returns the response as json or text.
 /// 
-/// Usage of data field does not yet work properly. Probably a communication issue between js and rust.
-/// To use json data please use the function postData located in `utils.js`.
+/// When `data` is set it is serialized to json and sent as request body.
 async fn send_request(url: String, method: Method, data: Option<&JsValue>, response_type: ResponseType) -> Result<JsValue, JsValue> {
     let mut opts = RequestInit::new();
     match method {
@@ -64,8 +91,8 @@ async fn send_request(url: String, method: Method, data: Option<&JsValue>, respo
     };
     opts.mode(RequestMode::Cors);
 
-    if data.is_some() {
-        opts.body(data);
+    if let Some(data) = data {
+        opts.body(Some(&JsValue::from(js_sys::JSON::stringify(data)?)));
     }
 
     let request = Request::new_with_str_and_init(&url, &opts)?;